@@ -0,0 +1,265 @@
+//! Converting many schemas at once.
+//!
+//! The input is a directory, searched recursively, or a glob pattern like
+//! `schemas/**/*.json`. Outputs mirror the layout of the inputs below the
+//! directory (or the part of the pattern before the first wildcard).
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::error::{Error, Result};
+use crate::input::InputFormat;
+
+/// Characters that make an input a glob pattern
+const GLOB_CHARS: &[char] = &['*', '?', '['];
+
+/// Schema files to convert, with the directory their outputs are mirrored from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inputs {
+    pub base: PathBuf,
+    /// Files in a stable, sorted order
+    pub files: Vec<PathBuf>,
+}
+
+/// Whether `input` names several schemas rather than one file
+pub fn is_batch(input: &Path) -> bool {
+    input.is_dir() || is_pattern(input)
+}
+
+fn is_pattern(input: &Path) -> bool {
+    input.to_string_lossy().contains(GLOB_CHARS)
+}
+
+/// Find the schema files a directory or glob pattern names
+///
+/// Directories only contribute `.json`, `.yaml` and `.yml` files, a pattern
+/// picks its own files.
+pub fn find_inputs(input: &Path) -> Result<Inputs> {
+    let mut files = Vec::new();
+    let base = if is_pattern(input) {
+        let pattern = input.to_string_lossy();
+        let paths = glob::glob(&pattern)
+            .map_err(|e| Error::Other(format!("Invalid pattern '{}': {}", pattern, e)))?;
+        for path in paths {
+            let path = path.map_err(|e| Error::Other(e.to_string()))?;
+            if path.is_file() {
+                files.push(path);
+            }
+        }
+        pattern_base(input)
+    } else {
+        find_schema_files(input, &mut files)?;
+        input.to_path_buf()
+    };
+    files.sort();
+
+    Ok(Inputs { base, files })
+}
+
+/// The directories of a pattern before its first wildcard
+fn pattern_base(pattern: &Path) -> PathBuf {
+    let mut base = PathBuf::new();
+    for component in pattern.components() {
+        if let Component::Normal(name) = component {
+            if name.to_string_lossy().contains(GLOB_CHARS) {
+                break;
+            }
+        }
+        base.push(component);
+    }
+    base
+}
+
+fn find_schema_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| Error::Other(format!("Failed to read '{}': {}", dir.display(), e)))?;
+    for entry in entries {
+        let path = entry.map_err(Error::Io)?.path();
+        if path.is_dir() {
+            find_schema_files(&path, files)?;
+        } else if InputFormat::from_path(&path).is_some() {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Where the output for `file` goes: the same path below `output_dir` as
+/// `file` has below `base`, with the extension replaced
+pub fn output_path(base: &Path, file: &Path, output_dir: &Path, extension: &str) -> PathBuf {
+    let relative = file.strip_prefix(base).unwrap_or(file);
+    output_dir.join(relative).with_extension(extension)
+}
+
+/// Fail if two inputs would be written to the same output, e.g. `foo.json`
+/// and `foo.yaml` next to each other
+///
+/// `outputs` are the output paths of `files`, in the same order.
+pub fn check_outputs(files: &[PathBuf], outputs: &[PathBuf]) -> Result<()> {
+    let mut written: HashMap<&Path, &Path> = HashMap::new();
+    let mut collisions = Vec::new();
+    for (file, output) in files.iter().zip(outputs) {
+        if let Some(other) = written.insert(output, file) {
+            collisions.push(format!(
+                "\n  '{}' and '{}' would both be written to '{}'",
+                other.display(),
+                file.display(),
+                output.display()
+            ));
+        }
+    }
+
+    if collisions.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Other(format!(
+            "Schemas have the same output, rename one of each:{}",
+            collisions.concat()
+        )))
+    }
+}
+
+/// Run `task` on every item using up to `jobs` threads
+///
+/// Results are in the order of `items`, a failing item doesn't stop the
+/// others.
+pub fn run_parallel<T, R, F>(items: &[T], jobs: usize, task: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(items.len()));
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                let result = task(item);
+                results
+                    .lock()
+                    .expect("a task panicked")
+                    .push((index, result));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().expect("a task panicked");
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Default number of threads, one per CPU
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_inputs_in_directory() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("devices/sensors")).unwrap();
+        for file in [
+            "root.json",
+            "devices/light.yaml",
+            "devices/sensors/thermo.yml",
+            "devices/README.md",
+        ] {
+            fs::write(dir.path().join(file), "{}").unwrap();
+        }
+
+        let inputs = find_inputs(dir.path()).unwrap();
+        assert_eq!(inputs.base, dir.path());
+        assert_eq!(
+            inputs.files,
+            vec![
+                dir.path().join("devices/light.yaml"),
+                dir.path().join("devices/sensors/thermo.yml"),
+                dir.path().join("root.json"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_inputs_by_pattern() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("v1/nested")).unwrap();
+        for file in ["v1/a.json", "v1/b.yaml", "v1/nested/c.json"] {
+            fs::write(dir.path().join(file), "{}").unwrap();
+        }
+
+        let inputs = find_inputs(&dir.path().join("v1/**/*.json")).unwrap();
+        assert_eq!(inputs.base, dir.path().join("v1"));
+        assert_eq!(
+            inputs.files,
+            vec![
+                dir.path().join("v1/a.json"),
+                dir.path().join("v1/nested/c.json")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pattern_base() {
+        assert_eq!(
+            pattern_base(Path::new("schemas/*.json")),
+            Path::new("schemas")
+        );
+        assert_eq!(pattern_base(Path::new("a/b*/c.json")), Path::new("a"));
+        assert_eq!(pattern_base(Path::new("*.json")), Path::new(""));
+    }
+
+    #[test]
+    fn test_output_path() {
+        assert_eq!(
+            output_path(
+                Path::new("schemas"),
+                Path::new("schemas/devices/light.yaml"),
+                Path::new("out"),
+                "html"
+            ),
+            Path::new("out/devices/light.html")
+        );
+    }
+
+    #[test]
+    fn test_check_outputs() {
+        let files = [
+            PathBuf::from("schemas/a.json"),
+            PathBuf::from("schemas/a.yaml"),
+            PathBuf::from("schemas/b.json"),
+        ];
+        let outputs: Vec<PathBuf> = files
+            .iter()
+            .map(|file| output_path(Path::new("schemas"), file, Path::new("out"), "html"))
+            .collect();
+
+        let err = check_outputs(&files, &outputs).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Schemas have the same output, rename one of each:\n  'schemas/a.json' and 'schemas/a.yaml' would both be written to 'out/a.html'"
+        );
+        assert!(check_outputs(&files[1..], &outputs[1..]).is_ok());
+    }
+
+    #[test]
+    fn test_run_parallel_keeps_order() {
+        let items: Vec<u32> = (0..50).collect();
+        let results = run_parallel(&items, 4, |n| n * 2);
+        assert_eq!(results, (0..50).map(|n| n * 2).collect::<Vec<_>>());
+
+        assert!(run_parallel(&[] as &[u32], 4, |n| *n).is_empty());
+    }
+}
@@ -0,0 +1,310 @@
+//! Validating instance documents against a schema.
+//!
+//! The schema is compiled once and every document is checked against it,
+//! collecting all errors rather than stopping at the first. References to
+//! other files are read from disk like in the rest of deckard, in JSON or
+//! YAML.
+
+use clap::ValueEnum;
+use jsonschema::error::ValidationErrorKind;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+use std::path::Path;
+use url::Url;
+
+use crate::error::{Error, Result};
+use crate::input;
+use crate::resolver;
+
+/// Format of the check report
+#[derive(ValueEnum, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// One line per error
+    #[default]
+    Text,
+    /// A JSON object, for CI
+    Json,
+}
+
+/// Why a document doesn't match the schema
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// JSON Pointer to the offending value in the document
+    pub instance_path: String,
+    /// The schema keyword that failed, e.g. `type` or `required`, or `false`
+    /// for a `false` subschema
+    pub keyword: String,
+    /// JSON Pointer to the failed keyword, through any `$ref`s
+    pub schema_path: String,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{}: {} ({} at #{})",
+            self.instance_path, self.message, self.keyword, self.schema_path
+        )
+    }
+}
+
+/// Result of checking one document
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// Name of the document, its path or `stdin`
+    pub name: String,
+    pub valid: bool,
+    pub errors: Vec<Violation>,
+}
+
+/// A schema compiled for checking documents
+pub struct Checker {
+    validator: jsonschema::Validator,
+}
+
+impl Checker {
+    /// Compile `schema`, `path` is where relative `$ref`s resolve from
+    /// (defaults to the current directory)
+    pub fn new(schema: &Value, path: Option<&Path>) -> Result<Self> {
+        let location = resolver::document_location(path)?;
+        let validator = jsonschema::options()
+            .with_base_uri(location.to_string())
+            .with_retriever(FileRetriever)
+            .build(schema)
+            .map_err(|e| Error::Other(format!("Unable to compile the schema: {}", e)))?;
+
+        Ok(Self { validator })
+    }
+
+    /// Every error in `instance`, sorted by location
+    pub fn check(&self, instance: &Value) -> Vec<Violation> {
+        let mut violations: Vec<Violation> = self
+            .validator
+            .iter_errors(instance)
+            .map(|error| {
+                let schema_path = error.schema_path.to_string();
+                // A `false` subschema fails as a whole, its path ends with
+                // whatever holds it (a property name, `items`...)
+                let keyword = match error.kind {
+                    ValidationErrorKind::FalseSchema => "false",
+                    _ => schema_path.rsplit('/').next().unwrap_or_default(),
+                };
+                Violation {
+                    instance_path: error.instance_path.to_string(),
+                    keyword: keyword.to_string(),
+                    schema_path,
+                    message: error.to_string(),
+                }
+            })
+            .collect();
+        violations.sort_by(|a, b| a.instance_path.cmp(&b.instance_path));
+
+        violations
+    }
+
+    /// Check a document and name it for the report
+    pub fn report(&self, name: impl Into<String>, instance: &Value) -> Report {
+        let errors = self.check(instance);
+        Report {
+            name: name.into(),
+            valid: errors.is_empty(),
+            errors,
+        }
+    }
+}
+
+/// Render reports in the given format, ending with a newline
+pub fn format_reports(reports: &[Report], format: ReportFormat) -> Result<String> {
+    Ok(match format {
+        ReportFormat::Text => reports
+            .iter()
+            .map(|report| {
+                if report.valid {
+                    format!("'{}' is valid.\n", report.name)
+                } else {
+                    let errors: String = report
+                        .errors
+                        .iter()
+                        .map(|error| format!("  {}\n", error))
+                        .collect();
+                    format!("'{}' is invalid:\n{}", report.name, errors)
+                }
+            })
+            .collect(),
+        ReportFormat::Json => {
+            let output = serde_json::json!({
+                "valid": reports.iter().all(|report| report.valid),
+                "documents": reports,
+            });
+            format!("{}\n", serde_json::to_string_pretty(&output)?)
+        }
+    })
+}
+
+impl Report {
+    /// A document that couldn't be read or parsed, failed with a `parse`
+    /// error at its root
+    pub fn unreadable(name: impl Into<String>, error: &Error) -> Self {
+        Report {
+            name: name.into(),
+            valid: false,
+            errors: vec![Violation {
+                instance_path: String::new(),
+                keyword: "parse".to_string(),
+                schema_path: String::new(),
+                message: error.to_string(),
+            }],
+        }
+    }
+}
+
+/// Reads referenced schemas from disk
+struct FileRetriever;
+
+impl jsonschema::Retrieve for FileRetriever {
+    fn retrieve(
+        &self,
+        uri: &jsonschema::Uri<String>,
+    ) -> std::result::Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let path = Url::parse(uri.as_str())?
+            .to_file_path()
+            .map_err(|_| format!("Unable to load '{}', only files are supported", uri))?;
+
+        Ok(input::read(&path, None)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_check() {
+        let schema = json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "port": {"$ref": "#/$defs/Port"},
+                "tags": {"type": "array", "items": {"type": "string"}}
+            },
+            "$defs": {"Port": {"type": "integer", "maximum": 65535}}
+        });
+        let checker = Checker::new(&schema, None).unwrap();
+
+        assert!(checker.check(&json!({"name": "a", "port": 80})).is_empty());
+
+        let violations = checker.check(&json!({"port": 70000, "tags": ["a", 1]}));
+        let summary: Vec<(&str, &str, &str)> = violations
+            .iter()
+            .map(|v| {
+                (
+                    v.instance_path.as_str(),
+                    v.keyword.as_str(),
+                    v.schema_path.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("", "required", "/required"),
+                ("/port", "maximum", "/properties/port/$ref/maximum"),
+                ("/tags/1", "type", "/properties/tags/items/type"),
+            ]
+        );
+        assert_eq!(
+            violations[0].to_string(),
+            "#: \"name\" is a required property (required at #/required)"
+        );
+    }
+
+    #[test]
+    fn test_external_reference() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("common.yaml"),
+            "$defs:\n  Id:\n    type: string\n    minLength: 3\n",
+        )
+        .unwrap();
+        let path = dir.path().join("schema.json");
+        let schema = json!({"properties": {"id": {"$ref": "common.yaml#/$defs/Id"}}});
+        let checker = Checker::new(&schema, Some(&path)).unwrap();
+
+        assert!(checker.check(&json!({"id": "abc"})).is_empty());
+        assert_eq!(checker.check(&json!({"id": "ab"}))[0].keyword, "minLength");
+    }
+
+    #[test]
+    fn test_false_subschema() {
+        let checker = Checker::new(
+            &json!({
+                "properties": {"legacy": false, "tags": {"items": false}},
+                "additionalProperties": false
+            }),
+            None,
+        )
+        .unwrap();
+        let violations = checker.check(&json!({"legacy": 1, "tags": [1], "extra": 1}));
+        let keywords: Vec<(&str, &str, &str)> = violations
+            .iter()
+            .map(|v| {
+                (
+                    v.instance_path.as_str(),
+                    v.keyword.as_str(),
+                    v.schema_path.as_str(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            keywords,
+            vec![
+                ("", "additionalProperties", "/additionalProperties"),
+                ("/legacy", "false", "/properties/legacy"),
+                ("/tags/0", "false", "/properties/tags/items"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_schema() {
+        let err = Checker::new(&json!({"type": 5}), None).err().unwrap();
+        assert!(err.to_string().starts_with("Unable to compile the schema"));
+    }
+
+    #[test]
+    fn test_format_reports() {
+        let checker = Checker::new(&json!({"type": "integer"}), None).unwrap();
+        let reports = vec![
+            checker.report("good.json", &json!(1)),
+            checker.report("bad.json", &json!("a")),
+        ];
+
+        assert_eq!(
+            format_reports(&reports, ReportFormat::Text).unwrap(),
+            "'good.json' is valid.\n'bad.json' is invalid:\n  #: \"a\" is not of type \"integer\" (type at #/type)\n"
+        );
+
+        let json: Value =
+            serde_json::from_str(&format_reports(&reports, ReportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["valid"], json!(false));
+        assert_eq!(
+            json["documents"][0],
+            json!({"name": "good.json", "valid": true, "errors": []})
+        );
+        assert_eq!(
+            json["documents"][1]["errors"][0],
+            json!({
+                "instance_path": "",
+                "keyword": "type",
+                "schema_path": "/type",
+                "message": "\"a\" is not of type \"integer\""
+            })
+        );
+    }
+}
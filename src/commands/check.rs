@@ -0,0 +1,75 @@
+use clap::Args as ClapArgs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use tracing::{debug, info};
+
+use crate::check::{self, Checker, Report, ReportFormat};
+use crate::error::{Error, Result};
+use crate::input::{self, InputFormat};
+
+#[derive(ClapArgs, Debug)]
+pub struct Args {
+    /// JSON Schema file to check against, in JSON or YAML
+    #[arg(short = 's', long = "schema", value_name = "FILE")]
+    pub schema: PathBuf,
+
+    /// Documents to check, in JSON or YAML (defaults to stdin)
+    #[arg(value_name = "FILE")]
+    pub instances: Vec<PathBuf>,
+
+    /// Format of the documents (detected from the file extension or content by default)
+    #[arg(long = "input-format", value_name = "FORMAT")]
+    pub input_format: Option<InputFormat>,
+
+    /// Format of the report
+    #[arg(
+        short = 'f',
+        long = "format",
+        value_name = "FORMAT",
+        default_value = "text"
+    )]
+    pub format: ReportFormat,
+}
+
+pub fn execute(args: Args) -> Result<()> {
+    info!("Checking documents.");
+
+    let (schema_source, schema) = input::load(Some(&args.schema), None)?;
+    debug!("Compiling schema: {}", schema_source);
+    let checker = Checker::new(&schema, Some(&args.schema))?;
+
+    let mut reports = Vec::new();
+    if args.instances.is_empty() {
+        reports.push(match input::load(None, args.input_format) {
+            Ok((name, instance)) => checker.report(name, &instance),
+            Err(e) => Report::unreadable("stdin", &e),
+        });
+    }
+    for path in &args.instances {
+        // An unreadable document fails on its own, the others are still checked
+        reports.push(match input::load(Some(path), args.input_format) {
+            Ok((name, instance)) => {
+                debug!("Checking: {}", name);
+                checker.report(name, &instance)
+            }
+            Err(e) => Report::unreadable(path.display().to_string(), &e),
+        });
+    }
+
+    io::stdout()
+        .write_all(check::format_reports(&reports, args.format)?.as_bytes())
+        .map_err(Error::Io)?;
+
+    let invalid = reports.iter().filter(|report| !report.valid).count();
+    if invalid > 0 {
+        return Err(Error::Other(format!(
+            "{} of {} document(s) don't match '{}'.",
+            invalid,
+            reports.len(),
+            schema_source
+        )));
+    }
+
+    info!("All documents match the schema.");
+    Ok(())
+}
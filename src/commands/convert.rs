@@ -1,83 +1,448 @@
 use clap::Args as ClapArgs;
 use std::fs;
-use std::io::{self, Read, Write};
-use std::path::PathBuf;
-use tracing::{debug, info};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use tracing::{debug, error, info, warn};
 
+use crate::batch;
+use crate::config::ConvertConfig;
 use crate::error::{Error, Result};
-use crate::html;
+use crate::html::{self, HtmlRenderer, Stylesheet};
+use crate::input::{self, InputFormat};
 use crate::json_schema;
+use crate::markdown::MarkdownRenderer;
+use crate::output::OutputFormat;
+use crate::render::Renderer;
+use crate::resolver;
+use crate::validation;
+use crate::watch;
 
 #[derive(ClapArgs, Debug)]
 pub struct Args {
-    /// Input JSON Schema file (defaults to stdin)
+    /// Input JSON Schema file, in JSON or YAML (defaults to stdin), or a
+    /// directory or glob pattern to convert every schema in
     #[arg(short = 'i', long = "input", value_name = "FILE")]
     pub input: Option<PathBuf>,
 
-    /// Output file (defaults to stdout)
+    /// Input format (detected from the file extension or content by default)
+    #[arg(long = "input-format", value_name = "FORMAT")]
+    pub input_format: Option<InputFormat>,
+
+    /// Render only the schema at this JSON Pointer, e.g. /$defs/NetworkConfig
+    /// or an OpenAPI /components/schemas/Pet
+    #[arg(long = "pointer", value_name = "POINTER")]
+    pub pointer: Option<String>,
+
+    /// Output file (defaults to stdout), or directory when converting several schemas
     #[arg(short = 'o', long = "output", value_name = "FILE")]
     pub output: Option<PathBuf>,
 
+    /// Number of schemas to convert at once [default: number of CPUs]
+    #[arg(short = 'j', long = "jobs", value_name = "JOBS")]
+    pub jobs: Option<usize>,
+
+    /// Output format [default: html]
+    #[arg(short = 'f', long = "format", value_name = "FORMAT")]
+    pub format: Option<OutputFormat>,
+
+    /// Minify the output HTML (the default, overrides the config file)
+    #[arg(long = "minify", overrides_with = "no_minify")]
+    pub minify: bool,
+
     /// Don't minify the output HTML
-    #[arg(long = "no-minify")]
+    #[arg(long = "no-minify", overrides_with = "minify")]
     pub no_minify: bool,
+
+    /// Regenerate the output whenever the input or a file it references changes
+    #[arg(short = 'w', long = "watch")]
+    pub watch: bool,
+
+    /// Fail instead of rendering when the input isn't a valid JSON Schema
+    #[arg(long = "strict")]
+    pub strict: bool,
+
+    /// Render the schema and property titles (the default, overrides the config file)
+    #[arg(long = "titles", overrides_with = "no_titles")]
+    pub titles: bool,
+
+    /// Don't render titles, e.g. when embedding under your own heading
+    #[arg(long = "no-titles", overrides_with = "titles")]
+    pub no_titles: bool,
+
+    /// Show the options of oneOf, anyOf and allOf as tabs in the HTML
+    #[arg(long = "tabs", overrides_with = "no_tabs")]
+    pub tabs: bool,
+
+    /// List the options one after another (the default, overrides the config file)
+    #[arg(long = "no-tabs", overrides_with = "tabs")]
+    pub no_tabs: bool,
+
+    /// Merge the members of allOf into one property list
+    #[arg(long = "merge-all-of", overrides_with = "no_merge_all_of")]
+    pub merge_all_of: bool,
+
+    /// List the members of allOf as separate options (the default, overrides the config file)
+    #[arg(long = "no-merge-all-of", overrides_with = "merge_all_of")]
+    pub no_merge_all_of: bool,
+
+    /// Add an example document generated from the schema
+    #[arg(long = "example", overrides_with = "no_example")]
+    pub example: bool,
+
+    /// Leave out the example (the default, overrides the config file)
+    #[arg(long = "no-example", overrides_with = "example")]
+    pub no_example: bool,
+
+    /// Maximum number of nested $refs to expand, deeper references are only linked [default: 8]
+    #[arg(long = "max-depth", value_name = "DEPTH")]
+    pub max_depth: Option<usize>,
+
+    /// Output a complete HTML document with a default stylesheet
+    #[arg(long = "standalone", overrides_with = "no_standalone")]
+    pub standalone: bool,
+
+    /// Output only the schema markup (the default, overrides the config file)
+    #[arg(long = "no-standalone", overrides_with = "standalone")]
+    pub no_standalone: bool,
+
+    /// Link this stylesheet instead of embedding the default one (implies --standalone)
+    #[arg(
+        long = "stylesheet",
+        value_name = "URL",
+        conflicts_with = "inline_stylesheet"
+    )]
+    pub stylesheet: Option<String>,
+
+    /// Embed this CSS file instead of the default stylesheet (implies --standalone)
+    #[arg(long = "inline-stylesheet", value_name = "FILE")]
+    pub inline_stylesheet: Option<PathBuf>,
+}
+
+/// Options for a conversion, after applying the config file
+#[derive(Debug)]
+struct Settings {
+    input: Option<PathBuf>,
+    input_format: Option<InputFormat>,
+    pointer: Option<String>,
+    output: Option<PathBuf>,
+    format: OutputFormat,
+    strict: bool,
+    watch: bool,
+    minify: bool,
+    titles: bool,
+    tabs: bool,
+    merge_all_of: bool,
+    example: bool,
+    max_depth: usize,
+    jobs: usize,
+    /// Stylesheet of the standalone document, `None` for markup only
+    standalone: Option<StylesheetSource>,
+}
+
+/// Where the stylesheet of a standalone document comes from
+#[derive(Debug)]
+enum StylesheetSource {
+    Default,
+    Link(String),
+    Inline(PathBuf),
 }
 
-pub fn execute(args: Args) -> Result<()> {
-    info!("Processing compilation to HTML.");
+impl Settings {
+    fn new(args: Args, config: &ConvertConfig) -> Self {
+        let minify = if args.minify || args.no_minify {
+            args.minify
+        } else {
+            config.minify.unwrap_or(true)
+        };
+
+        let titles = if args.titles || args.no_titles {
+            args.titles
+        } else {
+            config.titles.unwrap_or(true)
+        };
+
+        let tabs = if args.tabs || args.no_tabs {
+            args.tabs
+        } else {
+            config.tabs.unwrap_or(false)
+        };
+
+        let merge_all_of = if args.merge_all_of || args.no_merge_all_of {
+            args.merge_all_of
+        } else {
+            config.merge_all_of.unwrap_or(false)
+        };
+
+        let example = if args.example || args.no_example {
+            args.example
+        } else {
+            config.example.unwrap_or(false)
+        };
+
+        // A stylesheet from the command line replaces both config options
+        let stylesheet = match (args.stylesheet, args.inline_stylesheet) {
+            (Some(href), _) => Some(StylesheetSource::Link(href)),
+            (None, Some(path)) => Some(StylesheetSource::Inline(path)),
+            (None, None) => match (&config.stylesheet, &config.inline_stylesheet) {
+                (Some(href), _) => Some(StylesheetSource::Link(href.clone())),
+                (None, Some(path)) => Some(StylesheetSource::Inline(path.clone())),
+                (None, None) => None,
+            },
+        };
+        let standalone = if args.standalone || args.no_standalone {
+            args.standalone
+        } else {
+            config.standalone.unwrap_or(false) || stylesheet.is_some()
+        };
+
+        Self {
+            input: args.input.or_else(|| config.input.clone()),
+            input_format: args.input_format.or(config.input_format),
+            pointer: args.pointer.or_else(|| config.pointer.clone()),
+            output: args.output.or_else(|| config.output.clone()),
+            format: args.format.or(config.format).unwrap_or_default(),
+            strict: args.strict || config.strict.unwrap_or(false),
+            watch: args.watch,
+            minify,
+            titles,
+            tabs,
+            merge_all_of,
+            example,
+            max_depth: args
+                .max_depth
+                .or(config.max_depth)
+                .unwrap_or(json_schema::DEFAULT_MAX_DEPTH),
+            jobs: args
+                .jobs
+                .or(config.jobs)
+                .unwrap_or_else(batch::default_jobs),
+            standalone: match (standalone, stylesheet) {
+                (false, _) => None,
+                (true, stylesheet) => Some(stylesheet.unwrap_or(StylesheetSource::Default)),
+            },
+        }
+    }
+}
+
+pub fn execute(args: Args, config: &ConvertConfig) -> Result<()> {
+    let args = Settings::new(args, config);
+    debug!("Convert settings: {:?}", args);
+
+    let standalone = match &args.standalone {
+        Some(source) if args.format == OutputFormat::Html => {
+            debug!("Wrapping output in a standalone document.");
+            Some(read_stylesheet(source)?)
+        }
+        Some(_) => {
+            warn!("Standalone documents are only supported for HTML output, ignoring.");
+            None
+        }
+        None => None,
+    };
+    if args.tabs && args.format != OutputFormat::Html {
+        warn!("Tabs are only supported for HTML output, ignoring.");
+    }
+
+    if let Some(input) = args.input.as_deref().filter(|input| batch::is_batch(input)) {
+        if args.watch {
+            return Err(Error::Other(
+                "--watch only supports a single input file.".to_string(),
+            ));
+        }
+        return convert_batch(&args, input, standalone.as_ref());
+    }
+
+    if args.watch {
+        return convert_watch(&args, standalone.as_ref());
+    }
+
+    info!("Processing compilation to {}.", args.format);
+    let output = convert(&args, args.input.as_deref(), standalone.as_ref())?;
 
+    // Write output
+    write_output(&output, &args.output)?;
+
+    info!("Successfully converted to {}.", args.format);
+    Ok(())
+}
+
+/// Convert the schema read from `input`, or stdin without one
+fn convert(
+    args: &Settings,
+    input: Option<&Path>,
+    standalone: Option<&Stylesheet>,
+) -> Result<String> {
     // Read the schema
-    let (schema_source, schema) = if let Some(input_path) = &args.input {
-        // Read from file
-        debug!("Reading schema from: {}", input_path.display());
-
-        if !input_path.exists() {
-            return Err(Error::Other(format!(
-                "Input file '{}' not found.",
-                input_path.display()
-            )));
+    let (schema_source, schema) = input::load(input, args.input_format)?;
+
+    if args.strict {
+        let problems = validation::validate(&schema, input)?;
+        if !problems.is_empty() {
+            return Err(Error::Invalid {
+                name: schema_source,
+                problems,
+            });
         }
+    }
 
-        let content = fs::read_to_string(input_path).map_err(Error::Io)?;
-
-        let schema: serde_json::Value = serde_json::from_str(&content).map_err(|e| {
-            Error::Other(format!(
-                "Failed to parse JSON from '{}': {}",
-                input_path.display(),
-                e
-            ))
-        })?;
-
-        (format!("{}", input_path.display()), schema)
-    } else {
-        // Read from stdin
-        debug!("No input file specified, reading from stdin.");
-        let mut buffer = String::new();
-        io::stdin().read_to_string(&mut buffer).map_err(Error::Io)?;
-
-        let schema: serde_json::Value = serde_json::from_str(&buffer)?;
-        ("stdin".to_string(), schema)
+    debug!("Generating {} for: {}", args.format, schema_source);
+    let options = json_schema::Options {
+        path: input.map(Path::to_path_buf),
+        max_depth: args.max_depth,
+        merge_all_of: args.merge_all_of,
+        example: args.example,
+        pointer: args.pointer.clone(),
+        ..json_schema::Options::default()
     };
+    let document = json_schema::build_document(&schema, &options)?;
 
-    debug!("Generating HTML for: {}", schema_source);
-    let html = json_schema::generate_html(&schema)?;
+    Ok(match args.format {
+        OutputFormat::Html => {
+            let html = HtmlRenderer {
+                standalone: standalone.cloned(),
+                titles: args.titles,
+                tabs: args.tabs,
+            }
+            .render(&document)?;
 
-    // Minify by default (unless --no-minify is specified)
-    let final_html = if !args.no_minify {
-        debug!("Minifying HTML output.");
-        html::minify(&html)
-    } else {
-        debug!("Skipping HTML minification.");
-        html
+            // Minify by default (unless --no-minify is specified)
+            if args.minify {
+                debug!("Minifying HTML output.");
+                html::minify(&html)
+            } else {
+                debug!("Skipping HTML minification.");
+                html
+            }
+        }
+        OutputFormat::Markdown => MarkdownRenderer {
+            titles: args.titles,
+        }
+        .render(&document)?,
+    })
+}
+
+/// Convert every schema in a directory or matching a pattern into the
+/// output directory, carrying on past schemas that fail
+fn convert_batch(args: &Settings, input: &Path, standalone: Option<&Stylesheet>) -> Result<()> {
+    let Some(output_dir) = &args.output else {
+        return Err(Error::Other(
+            "Converting several schemas needs an --output directory.".to_string(),
+        ));
     };
 
-    // Write output
-    write_output(&final_html, &args.output)?;
+    let inputs = batch::find_inputs(input)?;
+    if inputs.files.is_empty() {
+        return Err(Error::Other(format!(
+            "No schemas found in '{}'.",
+            input.display()
+        )));
+    }
+    info!(
+        "Converting {} schema(s) to {} using {} job(s).",
+        inputs.files.len(),
+        args.format,
+        args.jobs
+    );
+
+    // Outputs are written in parallel, so they must not overlap
+    let outputs: Vec<PathBuf> = inputs
+        .files
+        .iter()
+        .map(|file| batch::output_path(&inputs.base, file, output_dir, args.format.extension()))
+        .collect();
+    batch::check_outputs(&inputs.files, &outputs)?;
+
+    let jobs: Vec<(&PathBuf, &PathBuf)> = inputs.files.iter().zip(&outputs).collect();
+    let results = batch::run_parallel(&jobs, args.jobs, |(file, path)| {
+        let output = convert(args, Some(file), standalone)?;
+
+        debug!("Writing output to: {}", path.display());
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(Error::Io)?;
+        }
+        fs::write(path, output).map_err(Error::Io)
+    });
+
+    let failures: Vec<String> = inputs
+        .files
+        .iter()
+        .zip(&results)
+        .filter_map(|(file, result)| {
+            let error = result.as_ref().err()?;
+            Some(format!("\n  {}: {}", file.display(), error))
+        })
+        .collect();
+
+    info!(
+        "Converted {} of {} schema(s) into '{}'.",
+        inputs.files.len() - failures.len(),
+        inputs.files.len(),
+        output_dir.display()
+    );
+    if !failures.is_empty() {
+        return Err(Error::Other(format!(
+            "{} schema(s) failed to convert:{}",
+            failures.len(),
+            failures.concat()
+        )));
+    }
 
-    info!("Successfully converted to HTML.");
     Ok(())
 }
 
+/// Convert the input file whenever it or a file it references changes,
+/// reporting failures without stopping
+fn convert_watch(args: &Settings, standalone: Option<&Stylesheet>) -> Result<()> {
+    let Some(input) = &args.input else {
+        return Err(Error::Other("--watch needs an --input file.".to_string()));
+    };
+    let output_name = match &args.output {
+        Some(path) => format!("'{}'", path.display()),
+        None => "stdout".to_string(),
+    };
+    info!(
+        "Watching '{}' for changes, press Ctrl-C to stop.",
+        input.display()
+    );
+
+    watch::watch(|| {
+        match convert(args, Some(input), standalone)
+            .and_then(|output| write_output(&output, &args.output))
+        {
+            Ok(()) => info!("Regenerated {}.", output_name),
+            Err(e) => error!("{}", e),
+        }
+
+        // Unparseable schemas can't tell which files they reference
+        let files = input::read(input, args.input_format)
+            .and_then(|schema| resolver::referenced_files(&schema, input));
+        match files {
+            Ok(files) => files,
+            Err(_) => resolver::document_location(Some(input))
+                .ok()
+                .and_then(|location| location.to_file_path().ok())
+                .into_iter()
+                .collect(),
+        }
+    })
+}
+
+fn read_stylesheet(source: &StylesheetSource) -> Result<Stylesheet> {
+    Ok(match source {
+        StylesheetSource::Default => Stylesheet::Default,
+        StylesheetSource::Link(href) => Stylesheet::Link(href.clone()),
+        StylesheetSource::Inline(path) => {
+            Stylesheet::Inline(fs::read_to_string(path).map_err(|e| {
+                Error::Other(format!(
+                    "Failed to read stylesheet '{}': {}",
+                    path.display(),
+                    e
+                ))
+            })?)
+        }
+    })
+}
+
 fn write_output(content: &str, output_path: &Option<PathBuf>) -> Result<()> {
     match output_path {
         Some(path) => {
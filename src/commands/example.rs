@@ -0,0 +1,71 @@
+use clap::Args as ClapArgs;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use tracing::{debug, info};
+
+use crate::error::{Error, Result};
+use crate::example::{self, ExampleFormat};
+use crate::input::{self, InputFormat};
+
+#[derive(ClapArgs, Debug)]
+pub struct Args {
+    /// Input JSON Schema file, in JSON or YAML (defaults to stdin)
+    #[arg(short = 'i', long = "input", value_name = "FILE")]
+    pub input: Option<PathBuf>,
+
+    /// Input format (detected from the file extension or content by default)
+    #[arg(long = "input-format", value_name = "FORMAT")]
+    pub input_format: Option<InputFormat>,
+
+    /// Generate a document for the schema at this JSON Pointer, e.g. /$defs/NetworkConfig
+    #[arg(long = "pointer", value_name = "POINTER")]
+    pub pointer: Option<String>,
+
+    /// Output file (defaults to stdout)
+    #[arg(short = 'o', long = "output", value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Format of the example
+    #[arg(
+        short = 'f',
+        long = "format",
+        value_name = "FORMAT",
+        default_value = "json"
+    )]
+    pub format: ExampleFormat,
+
+    /// Only include required properties
+    #[arg(long = "required-only")]
+    pub required_only: bool,
+}
+
+pub fn execute(args: Args) -> Result<()> {
+    info!("Generating example.");
+
+    let (schema_source, schema) = input::load(args.input.as_deref(), args.input_format)?;
+
+    debug!("Generating example for: {}", schema_source);
+    let options = example::Options {
+        path: args.input.clone(),
+        pointer: args.pointer.clone(),
+        required_only: args.required_only,
+    };
+    let example = example::generate(&schema, &options)?;
+    let output = args.format.format(&example)?;
+
+    match &args.output {
+        Some(path) => {
+            debug!("Writing example to: {}", path.display());
+            fs::write(path, output).map_err(Error::Io)?;
+        }
+        None => {
+            io::stdout()
+                .write_all(output.as_bytes())
+                .map_err(Error::Io)?;
+        }
+    }
+
+    info!("Successfully generated example.");
+    Ok(())
+}
@@ -1,7 +1,12 @@
 use clap::Subcommand;
 
+pub mod check;
 pub mod convert;
+pub mod example;
+pub mod serve;
+pub mod site;
 pub mod upgrade;
+pub mod validate;
 
 #[derive(Subcommand, Debug)]
 pub enum Commands {
@@ -12,4 +17,21 @@ pub enum Commands {
     /// Convert JSON Schema to HTML documentation
     #[command(visible_alias = "c")]
     Convert(convert::Args),
+
+    /// Check that the input is a well-formed JSON Schema
+    #[command(visible_alias = "v")]
+    Validate(validate::Args),
+
+    /// Generate an example document matching a JSON Schema
+    #[command(visible_alias = "e")]
+    Example(example::Args),
+
+    /// Check documents against a JSON Schema
+    Check(check::Args),
+
+    /// Preview rendered schemas in the browser, reloading on changes
+    Serve(serve::Args),
+
+    /// Generate a documentation site from a directory of schemas
+    Site(site::Args),
 }
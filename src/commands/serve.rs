@@ -0,0 +1,69 @@
+use clap::Args as ClapArgs;
+use std::fs;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use tracing::{debug, info};
+
+use crate::config::{self, ConvertConfig};
+use crate::error::{Error, Result};
+use crate::html::HtmlRenderer;
+use crate::json_schema;
+use crate::serve::{self, Preview};
+
+#[derive(ClapArgs, Debug)]
+pub struct Args {
+    /// Directory of the schemas to serve
+    #[arg(value_name = "DIR", default_value = ".")]
+    pub dir: PathBuf,
+
+    /// Port to listen on, on localhost
+    #[arg(
+        short = 'p',
+        long = "port",
+        value_name = "PORT",
+        default_value_t = 8000
+    )]
+    pub port: u16,
+}
+
+/// Serve the schemas, rendered with the `convert` options of the config file
+pub fn execute(args: Args, config: &ConvertConfig) -> Result<()> {
+    let root = fs::canonicalize(&args.dir)
+        .map_err(|e| Error::Other(format!("Unable to serve '{}': {}", args.dir.display(), e)))?;
+    if !root.is_dir() {
+        return Err(Error::Other(format!(
+            "Unable to serve '{}': not a directory",
+            args.dir.display()
+        )));
+    }
+
+    let stylesheet = config::stylesheet(config)?;
+    let preview = Preview {
+        root,
+        options: json_schema::Options {
+            path: None,
+            max_depth: config.max_depth.unwrap_or(json_schema::DEFAULT_MAX_DEPTH),
+            merge_all_of: config.merge_all_of.unwrap_or(false),
+            example: config.example.unwrap_or(false),
+            ..json_schema::Options::default()
+        },
+        renderer: HtmlRenderer {
+            standalone: None,
+            titles: config.titles.unwrap_or(true),
+            tabs: config.tabs.unwrap_or(false),
+        },
+        stylesheet,
+    };
+    debug!("Preview settings: {:?}", preview);
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| Error::Io(std::io::Error::other(e)))?;
+
+    info!("Starting preview server.");
+    runtime.block_on(serve::serve(
+        preview,
+        SocketAddr::from((Ipv4Addr::LOCALHOST, args.port)),
+    ))
+}
@@ -0,0 +1,86 @@
+use clap::Args as ClapArgs;
+use std::path::PathBuf;
+use tracing::{debug, info};
+
+use crate::batch;
+use crate::config::{self, ConvertConfig};
+use crate::error::{Error, Result};
+use crate::html::HtmlRenderer;
+use crate::json_schema;
+use crate::site::Site;
+
+#[derive(ClapArgs, Debug)]
+pub struct Args {
+    /// Directory of the schemas
+    #[arg(value_name = "DIR")]
+    pub dir: PathBuf,
+
+    /// Directory to write the site into
+    #[arg(short = 'o', long = "output", value_name = "DIR")]
+    pub output: PathBuf,
+
+    /// Title of the index page
+    #[arg(long = "title", value_name = "TITLE", default_value = "Schemas")]
+    pub title: String,
+
+    /// Number of pages to render at once [default: number of CPUs]
+    #[arg(short = 'j', long = "jobs", value_name = "JOBS")]
+    pub jobs: Option<usize>,
+}
+
+/// Generate the site, rendered with the `convert` options of the config file
+pub fn execute(args: Args, config: &ConvertConfig) -> Result<()> {
+    if !args.dir.is_dir() {
+        return Err(Error::Other(format!(
+            "Unable to generate a site from '{}': not a directory",
+            args.dir.display()
+        )));
+    }
+
+    let site = Site {
+        title: args.title,
+        options: json_schema::Options {
+            path: None,
+            max_depth: config.max_depth.unwrap_or(json_schema::DEFAULT_MAX_DEPTH),
+            merge_all_of: config.merge_all_of.unwrap_or(false),
+            example: config.example.unwrap_or(false),
+            ..json_schema::Options::default()
+        },
+        renderer: HtmlRenderer {
+            standalone: None,
+            titles: config.titles.unwrap_or(true),
+            tabs: config.tabs.unwrap_or(false),
+        },
+        stylesheet: config::stylesheet(config)?,
+        minify: config.minify.unwrap_or(true),
+        jobs: args
+            .jobs
+            .or(config.jobs)
+            .unwrap_or_else(batch::default_jobs),
+    };
+    debug!("Site settings: {:?}", site);
+
+    info!("Generating site from: {}", args.dir.display());
+    let summary = site.generate(&args.dir, &args.output)?;
+    info!(
+        "Generated {} of {} page(s) into '{}'.",
+        summary.schemas - summary.failures.len(),
+        summary.schemas,
+        args.output.display()
+    );
+
+    if summary.failures.is_empty() {
+        Ok(())
+    } else {
+        let failures: Vec<String> = summary
+            .failures
+            .iter()
+            .map(|(file, e)| format!("  {}: {}", file.display(), e))
+            .collect();
+        Err(Error::Other(format!(
+            "{} schema(s) failed to render:\n{}",
+            failures.len(),
+            failures.join("\n")
+        )))
+    }
+}
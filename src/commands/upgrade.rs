@@ -129,8 +129,8 @@ async fn get_release_info(args: &Args) -> Result<GithubResponse> {
 
     if !resp.status().is_success() {
         if resp.status() == 404 {
-            return Err(Error::Other(if args.version.is_some() {
-                format!("Release '{}' not found", args.version.as_ref().unwrap())
+            return Err(Error::Other(if let Some(version) = &args.version {
+                format!("Release '{}' not found", version)
             } else {
                 "No releases found for this project".to_string()
             }));
@@ -0,0 +1,36 @@
+use clap::Args as ClapArgs;
+use std::path::PathBuf;
+use tracing::{debug, info};
+
+use crate::error::{Error, Result};
+use crate::input::{self, InputFormat};
+use crate::validation;
+
+#[derive(ClapArgs, Debug)]
+pub struct Args {
+    /// Input JSON Schema file, in JSON or YAML (defaults to stdin)
+    #[arg(short = 'i', long = "input", value_name = "FILE")]
+    pub input: Option<PathBuf>,
+
+    /// Input format (detected from the file extension or content by default)
+    #[arg(long = "input-format", value_name = "FORMAT")]
+    pub input_format: Option<InputFormat>,
+}
+
+pub fn execute(args: Args) -> Result<()> {
+    info!("Validating schema.");
+
+    let (schema_source, schema) = input::load(args.input.as_deref(), args.input_format)?;
+
+    let problems = validation::validate(&schema, args.input.as_deref())?;
+    debug!("Found {} problem(s) in: {}", problems.len(), schema_source);
+    if !problems.is_empty() {
+        return Err(Error::Invalid {
+            name: schema_source,
+            problems,
+        });
+    }
+
+    info!("'{}' is a valid JSON Schema.", schema_source);
+    Ok(())
+}
@@ -0,0 +1,196 @@
+//! Configuration file support.
+//!
+//! The configuration file supplies defaults for command options, flags given
+//! on the command line always take precedence over it.
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::error::{Error, Result};
+use crate::html::Stylesheet;
+use crate::input::InputFormat;
+use crate::output::OutputFormat;
+
+/// Contents of the configuration file
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Defaults for the `convert` command
+    pub convert: ConvertConfig,
+}
+
+/// Defaults for the `convert` command
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConvertConfig {
+    /// Input JSON Schema file
+    pub input: Option<PathBuf>,
+    /// Format of the input (`json` or `yaml`)
+    pub input_format: Option<InputFormat>,
+    /// JSON Pointer of the subschema to document
+    pub pointer: Option<String>,
+    /// Output file
+    pub output: Option<PathBuf>,
+    /// Output format (`html` or `markdown`)
+    pub format: Option<OutputFormat>,
+    /// Minify the output HTML
+    pub minify: Option<bool>,
+    /// Fail on schemas that aren't valid JSON Schema
+    pub strict: Option<bool>,
+    /// Render the schema and property titles
+    pub titles: Option<bool>,
+    /// Show the options of compound schemas as tabs in the HTML
+    pub tabs: Option<bool>,
+    /// Merge the members of `allOf` into one property list
+    pub merge_all_of: Option<bool>,
+    /// Add an example document generated from the schema
+    pub example: Option<bool>,
+    /// Maximum number of nested `$ref`s to expand
+    pub max_depth: Option<usize>,
+    /// Number of schemas to convert at once
+    pub jobs: Option<usize>,
+    /// Wrap the output in a complete HTML document
+    pub standalone: Option<bool>,
+    /// Stylesheet URL to link from standalone documents
+    pub stylesheet: Option<String>,
+    /// CSS file to embed in standalone documents
+    pub inline_stylesheet: Option<PathBuf>,
+}
+
+impl Config {
+    /// Load the configuration file at `path`.
+    ///
+    /// A missing file is only an error when the path was given explicitly,
+    /// otherwise the defaults are used.
+    pub fn load(path: &Path, explicit: bool) -> Result<Self> {
+        if !path.exists() {
+            if explicit {
+                return Err(Error::Other(format!(
+                    "Config file '{}' not found.",
+                    path.display()
+                )));
+            }
+            debug!("No config file at '{}', using defaults.", path.display());
+            return Ok(Self::default());
+        }
+
+        debug!("Loading config from: {}", path.display());
+        let content = fs::read_to_string(path).map_err(Error::Io)?;
+        let mut config: Config = parse(path, &content)?;
+
+        // Paths in the file are relative to the file itself
+        if let Some(dir) = path.parent() {
+            config.convert.input = config.convert.input.map(|p| dir.join(p));
+            config.convert.output = config.convert.output.map(|p| dir.join(p));
+            config.convert.inline_stylesheet =
+                config.convert.inline_stylesheet.map(|p| dir.join(p));
+        }
+
+        Ok(config)
+    }
+}
+
+/// Stylesheet of served and site pages, from the `convert` options
+pub fn stylesheet(config: &ConvertConfig) -> Result<Stylesheet> {
+    Ok(match (&config.stylesheet, &config.inline_stylesheet) {
+        (Some(href), _) => Stylesheet::Link(href.clone()),
+        (None, Some(path)) => Stylesheet::Inline(fs::read_to_string(path).map_err(|e| {
+            Error::Other(format!(
+                "Failed to read stylesheet '{}': {}",
+                path.display(),
+                e
+            ))
+        })?),
+        (None, None) => Stylesheet::Default,
+    })
+}
+
+fn parse<T: DeserializeOwned>(path: &Path, content: &str) -> Result<T> {
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+
+    let parsed = if is_json {
+        serde_json::from_str(content).map_err(|e| e.to_string())
+    } else {
+        serde_yaml::from_str(content).map_err(|e| e.to_string())
+    };
+
+    parsed.map_err(|e| {
+        Error::Other(format!(
+            "Failed to parse config file '{}': {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_yaml() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("deckard.yaml");
+        fs::write(
+            &path,
+            "convert:\n  input: schema.json\n  minify: false\n  max_depth: 3\n",
+        )
+        .unwrap();
+
+        let config = Config::load(&path, true).unwrap();
+        assert_eq!(config.convert.input, Some(dir.path().join("schema.json")));
+        assert_eq!(config.convert.output, None);
+        assert_eq!(config.convert.minify, Some(false));
+        assert_eq!(config.convert.max_depth, Some(3));
+    }
+
+    #[test]
+    fn test_load_json() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("deckard.json");
+        fs::write(&path, r#"{"convert":{"output":"out/doc.html"}}"#).unwrap();
+
+        let config = Config::load(&path, true).unwrap();
+        assert_eq!(config.convert.output, Some(dir.path().join("out/doc.html")));
+    }
+
+    #[test]
+    fn test_load_empty() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("deckard.yaml");
+        fs::write(&path, "").unwrap();
+
+        assert_eq!(Config::load(&path, true).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_load_missing() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("deckard.yaml");
+
+        assert_eq!(Config::load(&path, false).unwrap(), Config::default());
+
+        let err = Config::load(&path, true).unwrap_err();
+        assert!(err.to_string().contains("not found"));
+    }
+
+    #[test]
+    fn test_load_malformed() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("deckard.yaml");
+
+        fs::write(&path, "convert: [").unwrap();
+        let err = Config::load(&path, false).unwrap_err();
+        assert!(err.to_string().contains("Failed to parse config file"));
+
+        fs::write(&path, "convert:\n  minfy: false\n").unwrap();
+        let err = Config::load(&path, false).unwrap_err();
+        assert!(err.to_string().contains("unknown field `minfy`"));
+    }
+}
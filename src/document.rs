@@ -0,0 +1,261 @@
+//! Intermediate model of the generated documentation.
+//!
+//! The schema is walked once into a [`Document`], which a
+//! [`Renderer`](crate::render::Renderer) turns into the output format. The
+//! model is already resolved: references are followed, recursive and too
+//! deeply nested ones are left as links.
+
+use serde_json::Value;
+use std::fmt;
+
+/// Documentation of a whole schema
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Document {
+    /// The schema `title`
+    pub title: Option<String>,
+    /// The schema `description`
+    pub description: Option<String>,
+    /// Sections in document order
+    pub sections: Vec<Section>,
+}
+
+/// A top-level section of the documentation
+#[derive(Debug, Clone, PartialEq)]
+pub enum Section {
+    /// Properties of an object schema
+    Properties(Vec<Property>),
+    /// Items of an array schema
+    ArrayItems(Box<Schema>),
+    /// Applicators of the root schema, such as `additionalProperties`
+    Applicators(Box<Applicators>),
+    /// `if`/`then`/`else` of the root schema
+    Conditional(Box<Conditional>),
+    /// Options of `oneOf`, `anyOf` or `allOf`
+    Compound(Compound),
+    /// Entries of `$defs` or `definitions`
+    Definitions(Vec<Definition>),
+    /// A sample document matching the schema
+    Example(Value),
+}
+
+/// A named property of an object schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct Property {
+    pub name: String,
+    pub required: bool,
+    pub schema: Schema,
+    /// The `allOf` member the property was merged from
+    pub origin: Option<String>,
+}
+
+/// Documentation of one (sub-)schema
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Schema {
+    /// Set for boolean schemas, `true` allows any value and `false` none
+    pub boolean: Option<bool>,
+    /// Human-friendly label from `title`
+    pub title: Option<String>,
+    /// Type shown in the type badge
+    pub schema_type: Option<String>,
+    pub description: Option<String>,
+    /// The `$ref` this schema is documented through
+    pub reference: Option<Reference>,
+    /// Annotated with `"deprecated": true`
+    pub deprecated: bool,
+    /// Annotated with `"readOnly": true`
+    pub read_only: bool,
+    /// Annotated with `"writeOnly": true`
+    pub write_only: bool,
+    pub constraints: Vec<Constraint>,
+    /// Allowed values from `enum`
+    pub enum_values: Option<Vec<Value>>,
+    pub default: Option<Value>,
+    pub examples: Vec<Value>,
+    /// Nested properties of an object schema
+    pub properties: Vec<Property>,
+    /// Items of an array schema
+    pub items: Option<Box<Schema>>,
+    pub applicators: Applicators,
+    pub conditional: Option<Conditional>,
+    /// Its `oneOf`, `anyOf` and `allOf`, without `allOf` when its members
+    /// are merged into `properties`
+    pub compounds: Vec<Compound>,
+}
+
+/// Subschemas applied to parts of an object or array, mostly from draft
+/// 2019-09 and 2020-12 keywords
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Applicators {
+    /// Schemas of the leading array items, by position
+    pub prefix_items: Vec<Schema>,
+    /// Schema of properties not covered by `properties` or
+    /// `patternProperties`
+    pub additional_properties: Option<Box<Schema>>,
+    pub pattern_properties: Vec<PatternProperty>,
+    /// Schema every property name must match
+    pub property_names: Option<Box<Schema>>,
+    /// Schema of properties no other keyword evaluated
+    pub unevaluated_properties: Option<Box<Schema>>,
+    pub contains: Option<Contains>,
+    /// Schema the value must not match
+    pub not: Option<Box<Schema>>,
+    /// Properties required when another property is present
+    pub dependent_required: Vec<DependentRequired>,
+    /// Schemas applied when a property is present
+    pub dependent_schemas: Vec<DependentSchema>,
+}
+
+impl Applicators {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Schemas applied depending on whether the value matches `if`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conditional {
+    /// The `if` schema
+    pub condition: Box<Schema>,
+    /// Applied when the condition matches
+    pub then_branch: Option<Box<Schema>>,
+    /// Applied when the condition doesn't match
+    pub else_branch: Option<Box<Schema>>,
+}
+
+/// Schema of the properties whose names match a pattern
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternProperty {
+    pub pattern: String,
+    pub schema: Schema,
+}
+
+/// Schema some array items must match
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contains {
+    pub schema: Box<Schema>,
+    /// `minContains`
+    pub min: Option<u64>,
+    /// `maxContains`
+    pub max: Option<u64>,
+}
+
+/// Properties required when `property` is present
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependentRequired {
+    pub property: String,
+    pub required: Vec<String>,
+}
+
+/// Schema applied when `property` is present
+#[derive(Debug, Clone, PartialEq)]
+pub struct DependentSchema {
+    pub property: String,
+    pub schema: Schema,
+}
+
+/// A `$ref` and where it leads
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    /// The `$ref` value as written
+    pub text: String,
+    /// Root definition the reference targets, if any, or the definition in
+    /// the document `href` leads to
+    pub definition: Option<String>,
+    /// Page the target is documented on, for references into schemas that
+    /// have pages of their own
+    pub href: Option<String>,
+    /// The target is already being documented, so it isn't expanded again
+    pub recursive: bool,
+    /// The target is documented in place, false when it's only linked
+    /// because it's recursive or too deeply nested
+    pub expanded: bool,
+}
+
+/// A validation constraint such as `minLength: 1`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraint {
+    pub label: String,
+    /// Value of the constraint, `None` for flags like `uniqueItems`
+    pub value: Option<String>,
+}
+
+impl Constraint {
+    pub fn new(label: impl Into<String>, value: impl ToString) -> Self {
+        Self {
+            label: label.into(),
+            value: Some(value.to_string()),
+        }
+    }
+
+    pub fn flag(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            value: None,
+        }
+    }
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "{}: {}", self.label, value),
+            None => write!(f, "{}", self.label),
+        }
+    }
+}
+
+/// Options of a compound schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct Compound {
+    pub kind: CompoundKind,
+    /// Property whose value tells the options apart
+    pub discriminator: Option<String>,
+    pub options: Vec<CompoundOption>,
+}
+
+/// One option of a compound schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompoundOption {
+    /// The discriminator value, `title` or referenced definition naming the
+    /// option, if any
+    pub label: Option<String>,
+    pub schema: Schema,
+}
+
+/// Keyword of a compound schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompoundKind {
+    /// `oneOf`
+    One,
+    /// `anyOf`
+    Any,
+    /// `allOf`
+    All,
+}
+
+impl CompoundKind {
+    /// The JSON Schema keyword
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            CompoundKind::One => "oneOf",
+            CompoundKind::Any => "anyOf",
+            CompoundKind::All => "allOf",
+        }
+    }
+
+    /// Heading of the section
+    pub fn label(&self) -> &'static str {
+        match self {
+            CompoundKind::One => "One Of",
+            CompoundKind::Any => "Any Of",
+            CompoundKind::All => "All Of",
+        }
+    }
+}
+
+/// An entry of `$defs` or `definitions`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Definition {
+    pub name: String,
+    pub schema: Schema,
+}
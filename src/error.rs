@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::validation::Problem;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Error, Debug)]
@@ -10,6 +12,22 @@ pub enum Error {
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("YAML error: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    #[error("'{name}' is not a valid JSON Schema:{}", list_problems(.problems))]
+    Invalid {
+        name: String,
+        problems: Vec<Problem>,
+    },
+
     #[error("{0}")]
     Other(String),
 }
+
+fn list_problems(problems: &[Problem]) -> String {
+    problems
+        .iter()
+        .map(|problem| format!("\n  {}", problem))
+        .collect()
+}
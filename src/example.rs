@@ -0,0 +1,534 @@
+//! Sample documents synthesized from a schema.
+//!
+//! Values are taken from `const`, `examples` (or OpenAPI's `example`),
+//! `default` and `enum` where the schema has them, and are otherwise
+//! placeholders matching the type and `format`, within the numeric and
+//! length bounds.
+
+use clap::ValueEnum;
+use serde_json::{Map, Value};
+use std::path::PathBuf;
+use tracing::debug;
+
+use crate::error::Result;
+use crate::resolver::{self, Resolver, Scope};
+
+/// Format of a generated example
+#[derive(ValueEnum, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ExampleFormat {
+    /// Pretty-printed JSON
+    #[default]
+    Json,
+    /// YAML
+    Yaml,
+}
+
+impl ExampleFormat {
+    /// Serialize an example, ending with a newline
+    pub fn format(&self, example: &Value) -> Result<String> {
+        Ok(match self {
+            ExampleFormat::Json => format!("{}\n", serde_json::to_string_pretty(example)?),
+            ExampleFormat::Yaml => serde_yaml::to_string(example)?,
+        })
+    }
+}
+
+/// Options controlling example generation
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Path of the schema file, relative `$ref`s are resolved against it
+    /// (defaults to the current directory)
+    pub path: Option<PathBuf>,
+    /// JSON Pointer to the schema to generate a document for, the whole
+    /// document when `None`
+    pub pointer: Option<String>,
+    /// Leave out properties that aren't required
+    pub required_only: bool,
+}
+
+/// Generate a document matching `schema`
+///
+/// Recursive references are only followed once, so a tree `Node` gets no
+/// `children`. Schemas nothing can match, like `false`, give `null`.
+pub fn generate(schema: &Value, options: &Options) -> Result<Value> {
+    let (selected, pointer) = resolver::select(schema, options.pointer.as_deref().unwrap_or(""))?;
+    let location = resolver::document_location(options.path.as_deref())?;
+    let resolver = Resolver::new(schema, location.clone());
+    let scope = resolver::scope_at(schema, &location, &pointer);
+
+    let mut generator = Generator {
+        resolver,
+        expanding: vec![format!("{}#{}", location, pointer)],
+        required_only: options.required_only,
+    };
+
+    Ok(generator.sample_in(selected, &scope).unwrap_or(Value::Null))
+}
+
+struct Generator {
+    resolver: Resolver,
+    /// Canonical ids of the references being followed
+    expanding: Vec<String>,
+    required_only: bool,
+}
+
+impl Generator {
+    /// A value for `schema`, `None` if there's none to give
+    ///
+    /// `scope` is the scope of the parent schema.
+    fn sample(&mut self, schema: &Value, scope: &Scope) -> Option<Value> {
+        self.sample_in(schema, &scope.enter(schema))
+    }
+
+    /// Like `sample`, with `scope` already the scope of `schema` itself
+    fn sample_in(&mut self, schema: &Value, scope: &Scope) -> Option<Value> {
+        match schema {
+            Value::Bool(true) => return Some(Value::Null),
+            Value::Object(_) => {}
+            _ => return None,
+        }
+
+        let Some(reference) = schema.get("$ref").and_then(|v| v.as_str()) else {
+            return self.sample_resolved(schema, scope);
+        };
+        let Some(target) = self.resolver.resolve(scope, reference) else {
+            debug!("Unable to resolve reference: {}", reference);
+            return None;
+        };
+        let id = target.id();
+        if self.expanding.contains(&id) {
+            debug!("Not following recursive reference: {}", reference);
+            return None;
+        }
+
+        self.expanding.push(id);
+        let value = self.sample_resolved(&resolver::merge(schema, target.schema), &target.scope);
+        self.expanding.pop();
+
+        value
+    }
+
+    fn sample_resolved(&mut self, schema: &Value, scope: &Scope) -> Option<Value> {
+        if let Some(value) = schema.get("const") {
+            return Some(value.clone());
+        }
+        if let Some(value) = schema
+            .get("examples")
+            .and_then(|v| v.as_array())
+            .and_then(|v| v.first())
+        {
+            return Some(value.clone());
+        }
+        if let Some(value) = schema.get("example") {
+            return Some(value.clone());
+        }
+        if let Some(value) = schema.get("default") {
+            return Some(value.clone());
+        }
+        if let Some(value) = schema
+            .get("enum")
+            .and_then(|v| v.as_array())
+            .and_then(|v| v.first())
+        {
+            return Some(value.clone());
+        }
+
+        let mut value = self.sample_type(schema, scope);
+
+        // Every `allOf` member applies, the first option of the others will do
+        let mut members: Vec<&Value> = schema
+            .get("allOf")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .collect();
+        for keyword in ["oneOf", "anyOf"] {
+            if let Some(first) = schema
+                .get(keyword)
+                .and_then(|v| v.as_array())
+                .and_then(|v| v.first())
+            {
+                members.push(first);
+            }
+        }
+        for member in members {
+            let Some(member_value) = self.sample(member, scope) else {
+                continue;
+            };
+            value = Some(match (value, member_value) {
+                (Some(Value::Object(mut own)), Value::Object(merged)) => {
+                    for (key, item) in merged {
+                        own.entry(key).or_insert(item);
+                    }
+                    Value::Object(own)
+                }
+                (None | Some(Value::Null), member_value) => member_value,
+                (Some(own), _) => own,
+            });
+        }
+
+        value
+    }
+
+    fn sample_type(&mut self, schema: &Value, scope: &Scope) -> Option<Value> {
+        let value = match get_type(schema)? {
+            "object" => self.sample_object(schema, scope),
+            "array" => self.sample_array(schema, scope),
+            "string" => Value::String(sample_string(schema)),
+            "integer" => sample_number(schema, true),
+            "number" => sample_number(schema, false),
+            "boolean" => Value::Bool(true),
+            _ => Value::Null,
+        };
+        Some(value)
+    }
+
+    fn sample_object(&mut self, schema: &Value, scope: &Scope) -> Value {
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str())
+            .collect();
+        let mut object = Map::new();
+
+        if let Some(properties) = schema.get("properties").and_then(|v| v.as_object()) {
+            for (name, property) in properties {
+                if self.required_only && !required.contains(&name.as_str()) {
+                    continue;
+                }
+                if let Some(value) = self.sample(property, scope) {
+                    object.insert(name.clone(), value);
+                }
+            }
+        }
+
+        // Required properties without a schema of their own
+        for name in required {
+            if !object.contains_key(name) {
+                let value = schema
+                    .get("additionalProperties")
+                    .and_then(|additional| self.sample(additional, scope))
+                    .unwrap_or(Value::Null);
+                object.insert(name.to_string(), value);
+            }
+        }
+
+        Value::Object(object)
+    }
+
+    fn sample_array(&mut self, schema: &Value, scope: &Scope) -> Value {
+        // Draft 2019-09 and earlier use an `items` array for tuples
+        let (prefix, items) = match (schema.get("prefixItems"), schema.get("items")) {
+            (Some(Value::Array(prefix)), items) => (prefix.as_slice(), items),
+            (None, Some(Value::Array(prefix))) => (prefix.as_slice(), None),
+            (_, items) => (&[][..], items),
+        };
+
+        let mut array: Vec<Value> = prefix
+            .iter()
+            .map(|item| self.sample(item, scope).unwrap_or(Value::Null))
+            .collect();
+
+        if let Some(items) = items {
+            let min_items = schema.get("minItems").and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+            // One item shows the shape, unless the tuple already does
+            let count = min_items.max(usize::from(prefix.is_empty()));
+            while array.len() < count {
+                match self.sample(items, scope) {
+                    Some(value) => array.push(value),
+                    None => break,
+                }
+            }
+        }
+
+        Value::Array(array)
+    }
+}
+
+/// The declared type, the first non-null one of a union, or one implied by
+/// the keywords present
+fn get_type(schema: &Value) -> Option<&str> {
+    match schema.get("type") {
+        Some(Value::String(schema_type)) => return Some(schema_type),
+        Some(Value::Array(types)) => {
+            let types: Vec<&str> = types.iter().filter_map(|t| t.as_str()).collect();
+            return types
+                .iter()
+                .find(|t| **t != "null")
+                .or(types.first())
+                .copied();
+        }
+        _ => {}
+    }
+
+    let has = |keywords: &[&str]| keywords.iter().any(|k| schema.get(k).is_some());
+    if has(&[
+        "properties",
+        "required",
+        "additionalProperties",
+        "patternProperties",
+    ]) {
+        Some("object")
+    } else if has(&["items", "prefixItems", "minItems", "maxItems"]) {
+        Some("array")
+    } else if has(&["minLength", "maxLength", "pattern", "format"]) {
+        Some("string")
+    } else if has(&[
+        "minimum",
+        "maximum",
+        "exclusiveMinimum",
+        "exclusiveMaximum",
+        "multipleOf",
+    ]) {
+        Some("number")
+    } else {
+        None
+    }
+}
+
+fn sample_string(schema: &Value) -> String {
+    let format = schema.get("format").and_then(|v| v.as_str());
+    let mut sample = match format {
+        Some("date-time") => "2024-01-01T12:00:00Z",
+        Some("date") => "2024-01-01",
+        Some("time") => "12:00:00",
+        Some("duration") => "P1D",
+        Some("email" | "idn-email") => "user@example.com",
+        Some("hostname" | "idn-hostname") => "example.com",
+        Some("ipv4") => "192.0.2.1",
+        Some("ipv6") => "2001:db8::1",
+        Some("uri" | "iri" | "uri-reference" | "iri-reference" | "url") => "https://example.com",
+        Some("uuid") => "123e4567-e89b-12d3-a456-426614174000",
+        _ => "string",
+    }
+    .to_string();
+
+    let length = |keyword: &str| {
+        schema
+            .get(keyword)
+            .and_then(|v| v.as_u64())
+            .map(|n| n as usize)
+    };
+    if let Some(min) = length("minLength") {
+        while sample.chars().count() < min {
+            sample.push('x');
+        }
+    }
+    if let Some(max) = length("maxLength") {
+        sample = sample.chars().take(max).collect();
+    }
+
+    sample
+}
+
+/// The smallest value within the bounds, or zero if that's allowed
+fn sample_number(schema: &Value, integer: bool) -> Value {
+    let get = |keyword: &str| schema.get(keyword).and_then(|v| v.as_f64());
+    // Draft-04 marks the bound itself as exclusive
+    let draft4_exclusive =
+        |keyword: &str| schema.get(keyword).and_then(|v| v.as_bool()) == Some(true);
+    let step = if integer { 1.0 } else { 0.5 };
+
+    let lower = match (get("minimum"), get("exclusiveMinimum")) {
+        (_, Some(exclusive)) => Some(exclusive + step),
+        (Some(min), None) if draft4_exclusive("exclusiveMinimum") => Some(min + step),
+        (min, None) => min,
+    };
+    let upper = match (get("maximum"), get("exclusiveMaximum")) {
+        (_, Some(exclusive)) => Some(exclusive - step),
+        (Some(max), None) if draft4_exclusive("exclusiveMaximum") => Some(max - step),
+        (max, None) => max,
+    };
+
+    let mut value = match (lower, upper) {
+        (Some(lower), _) => lower,
+        (None, Some(upper)) if upper < 0.0 => upper,
+        (None, _) => 0.0,
+    };
+    if integer {
+        value = value.ceil();
+    }
+    if let Some(multiple) = get("multipleOf").filter(|m| *m > 0.0) {
+        value = (value / multiple).ceil() * multiple;
+    }
+
+    if value.fract() == 0.0 {
+        Value::from(value as i64)
+    } else {
+        Value::from(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn example(schema: Value) -> Value {
+        generate(&schema, &Options::default()).unwrap()
+    }
+
+    #[test]
+    fn test_annotated_values() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "kind": {"type": "string", "const": "sensor"},
+                "name": {"type": "string", "examples": ["Kitchen"], "default": "unnamed"},
+                "unit": {"type": "string", "default": "celsius"},
+                "mode": {"enum": ["auto", "manual"]},
+                "room": {"type": "string", "example": "Hall", "default": "none"}
+            }
+        });
+
+        assert_eq!(
+            example(schema),
+            json!({"kind": "sensor", "name": "Kitchen", "unit": "celsius", "mode": "auto", "room": "Hall"})
+        );
+    }
+
+    #[test]
+    fn test_pointer() {
+        let schema = json!({
+            "openapi": "3.1.0",
+            "components": {"schemas": {
+                "Light": {"type": "object", "properties": {"room": {"$ref": "#/components/schemas/Room"}}},
+                "Room": {"type": "string", "example": "Hall"}
+            }}
+        });
+        let options = Options {
+            pointer: Some("#/components/schemas/Light".to_string()),
+            ..Options::default()
+        };
+
+        assert_eq!(
+            generate(&schema, &options).unwrap(),
+            json!({"room": "Hall"})
+        );
+    }
+
+    #[test]
+    fn test_placeholders() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "string", "format": "uuid"},
+                "code": {"type": "string", "minLength": 8},
+                "count": {"type": "integer", "minimum": 3},
+                "ratio": {"type": "number", "exclusiveMinimum": 0, "multipleOf": 0.25},
+                "offset": {"type": "integer", "maximum": -10},
+                "enabled": {"type": "boolean"},
+                "note": {"type": ["string", "null"], "maxLength": 3},
+                "tags": {"type": "array", "items": {"type": "string"}},
+                "pair": {"prefixItems": [{"type": "integer"}, {"type": "boolean"}]}
+            }
+        });
+
+        assert_eq!(
+            example(schema),
+            json!({
+                "id": "123e4567-e89b-12d3-a456-426614174000",
+                "code": "stringxx",
+                "count": 3,
+                "ratio": 0.5,
+                "offset": -10,
+                "enabled": true,
+                "note": "str",
+                "tags": ["string"],
+                "pair": [0, true]
+            })
+        );
+    }
+
+    #[test]
+    fn test_required_only() {
+        let schema = json!({
+            "type": "object",
+            "required": ["id", "extra"],
+            "properties": {"id": {"type": "integer"}, "name": {"type": "string"}}
+        });
+        let options = Options {
+            required_only: true,
+            ..Options::default()
+        };
+
+        assert_eq!(
+            generate(&schema, &options).unwrap(),
+            json!({"id": 0, "extra": null})
+        );
+    }
+
+    #[test]
+    fn test_references_and_compounds() {
+        let schema = json!({
+            "$ref": "#/$defs/Node",
+            "$defs": {
+                "Node": {
+                    "allOf": [{"$ref": "#/$defs/Named"}],
+                    "oneOf": [
+                        {"properties": {"kind": {"const": "leaf"}}},
+                        {"properties": {"kind": {"const": "branch"}}}
+                    ],
+                    "properties": {
+                        "children": {"type": "array", "items": {"$ref": "#/$defs/Node"}}
+                    }
+                },
+                "Named": {"properties": {"name": {"type": "string"}}}
+            }
+        });
+
+        assert_eq!(
+            example(schema),
+            json!({"children": [], "name": "string", "kind": "leaf"})
+        );
+    }
+
+    #[test]
+    fn test_relative_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("schemas").join("sub");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            temp_dir.path().join("schemas").join("device.json"),
+            r#"{"$id": "sub/device.json", "$ref": "name.json"}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("name.json"),
+            r#"{"type": "string", "example": "Hall"}"#,
+        )
+        .unwrap();
+
+        let schema = json!({
+            "$id": "schemas/root.json",
+            "type": "object",
+            "properties": {"device": {"$ref": "device.json"}}
+        });
+        let options = Options {
+            path: Some(temp_dir.path().join("root.json")),
+            ..Options::default()
+        };
+
+        assert_eq!(
+            generate(&schema, &options).unwrap(),
+            json!({"device": "Hall"})
+        );
+    }
+
+    #[test]
+    fn test_formats() {
+        let example = json!({"a": [1, {"b": null}]});
+        assert_eq!(
+            ExampleFormat::Json.format(&example).unwrap(),
+            "{\n  \"a\": [\n    1,\n    {\n      \"b\": null\n    }\n  ]\n}\n"
+        );
+        assert_eq!(
+            ExampleFormat::Yaml.format(&example).unwrap(),
+            "a:\n- 1\n- b: null\n"
+        );
+    }
+}
@@ -1,3 +1,619 @@
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde_json::Value;
+use std::cell::Cell;
+use std::path::Path;
+
+use crate::document::{
+    Applicators, Compound, Conditional, Definition, Document, Property, Reference, Schema, Section,
+};
+use crate::error::Result;
+use crate::json_schema::{escape_html, format_json_value};
+use crate::render::Renderer;
+
+/// Stylesheet bundled into standalone documents by default
+pub const DEFAULT_STYLESHEET: &str = include_str!("assets/default.css");
+
+/// Characters escaped in URL path segments and query values
+pub const URL_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Title used for standalone documents when the schema has none
+const DEFAULT_TITLE: &str = "Schema Documentation";
+
+/// Styling of a standalone document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stylesheet {
+    /// Embed the built-in stylesheet
+    Default,
+    /// Link to an external stylesheet
+    Link(String),
+    /// Embed the given CSS
+    Inline(String),
+}
+
+/// Relative URL of a relative file path, with each segment percent-encoded
+pub fn path_href(path: &Path) -> String {
+    path.components()
+        .map(|component| {
+            utf8_percent_encode(&component.as_os_str().to_string_lossy(), URL_COMPONENT).to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Wrap a markup fragment in a complete HTML document
+pub fn document(title: Option<&str>, body: &str, stylesheet: &Stylesheet) -> String {
+    let style = match stylesheet {
+        Stylesheet::Default => format!("<style>\n{}</style>", DEFAULT_STYLESHEET),
+        Stylesheet::Link(href) => {
+            format!("<link rel=\"stylesheet\" href=\"{}\">", escape_html(href))
+        }
+        Stylesheet::Inline(css) => format!("<style>\n{}</style>", css),
+    };
+
+    format!(
+        "<!DOCTYPE html>\n\
+         <html lang=\"en\">\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n\
+         {}\n\
+         </head>\n\
+         <body>\n\
+         {}\n\
+         </body>\n\
+         </html>\n",
+        escape_html(title.unwrap_or(DEFAULT_TITLE)),
+        style,
+        body
+    )
+}
+
+/// Renders semantic HTML markup, see `docs/css-classes.md` for the classes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlRenderer {
+    /// Wrap the markup in a complete document with this stylesheet
+    pub standalone: Option<Stylesheet>,
+    /// Render the schema and property titles
+    pub titles: bool,
+    /// Show the options of compound schemas as tabs
+    pub tabs: bool,
+}
+
+impl Default for HtmlRenderer {
+    fn default() -> Self {
+        Self {
+            standalone: None,
+            titles: true,
+            tabs: false,
+        }
+    }
+}
+
+impl Renderer for HtmlRenderer {
+    fn render(&self, document: &Document) -> Result<String> {
+        let mut html = String::new();
+        let writer = Writer {
+            renderer: self,
+            compounds: Cell::new(0),
+        };
+
+        // Main container
+        html.push_str("<div class=\"schema-container\">");
+
+        // Schema header
+        html.push_str("<div class=\"schema-header\">");
+        if let Some(title) = document.title.as_ref().filter(|_| self.titles) {
+            html.push_str(&format!(
+                "<h1 class=\"schema-title\">{}</h1>",
+                escape_html(title)
+            ));
+        }
+        if let Some(description) = &document.description {
+            html.push_str(&format!(
+                "<p class=\"schema-description\">{}</p>",
+                escape_html(description)
+            ));
+        }
+        html.push_str("</div>");
+
+        for section in &document.sections {
+            match section {
+                Section::Properties(properties) => {
+                    html.push_str("<div class=\"properties-section\">");
+                    html.push_str("<h2>Properties</h2>");
+                    html.push_str("<div class=\"properties-list\">");
+                    for property in properties {
+                        html.push_str(&writer.render_property(property, 0));
+                    }
+                    html.push_str("</div>");
+                    html.push_str("</div>");
+                }
+                Section::ArrayItems(items) => {
+                    html.push_str("<div class=\"array-section\">");
+                    html.push_str("<h2>Array Items</h2>");
+                    html.push_str(&writer.render_schema_details(items, 0));
+                    html.push_str("</div>");
+                }
+                Section::Applicators(applicators) => {
+                    html.push_str("<div class=\"applicators-section\">");
+                    html.push_str(&writer.render_applicators(applicators, 0));
+                    html.push_str("</div>");
+                }
+                Section::Conditional(conditional) => {
+                    html.push_str("<div class=\"conditional-section\">");
+                    html.push_str("<h2>Conditional</h2>");
+                    html.push_str(&writer.render_conditional(conditional, 0));
+                    html.push_str("</div>");
+                }
+                Section::Compound(compound) => {
+                    html.push_str(&writer.render_compound(compound, 3, 0, ""))
+                }
+                Section::Definitions(definitions) => {
+                    html.push_str("<div class=\"definitions-section\">");
+                    html.push_str("<h2>Definitions</h2>");
+                    for definition in definitions {
+                        html.push_str(&writer.render_definition(definition));
+                    }
+                    html.push_str("</div>");
+                }
+                Section::Example(example) => {
+                    html.push_str("<div class=\"example-section\">");
+                    html.push_str("<h2>Example</h2>");
+                    html.push_str(&format!(
+                        "<pre class=\"example\"><code>{}</code></pre>",
+                        escape_html(&serde_json::to_string_pretty(example)?)
+                    ));
+                    html.push_str("</div>");
+                }
+            }
+        }
+
+        html.push_str("</div>");
+
+        Ok(match &self.standalone {
+            Some(stylesheet) => self::document(document.title.as_deref(), &html, stylesheet),
+            None => html,
+        })
+    }
+}
+
+/// State of one `render` call
+struct Writer<'r> {
+    renderer: &'r HtmlRenderer,
+    /// Compounds of subschemas rendered so far, numbering their tab groups
+    compounds: Cell<usize>,
+}
+
+impl Writer<'_> {
+    fn render_property(&self, property: &Property, depth: usize) -> String {
+        let mut html = String::new();
+        let schema = &property.schema;
+
+        html.push_str(&format!(
+            "<div class=\"property depth-{}\" data-property=\"{}\">",
+            depth,
+            escape_html(&property.name)
+        ));
+
+        html.push_str("<div class=\"property-header\">");
+        html.push_str(&format!(
+            "<span class=\"property-name\">{}</span>",
+            escape_html(&property.name)
+        ));
+
+        if let Some(title) = schema.title.as_ref().filter(|_| self.renderer.titles) {
+            html.push_str(&format!(
+                " <span class=\"property-title\">{}</span>",
+                escape_html(title)
+            ));
+        }
+
+        if let Some(prop_type) = &schema.schema_type {
+            html.push_str(&format!(
+                " <span class=\"type-badge\">{}</span>",
+                escape_html(prop_type)
+            ));
+        }
+
+        if property.required {
+            html.push_str(" <span class=\"required-badge\">required</span>");
+        }
+
+        let annotations = [
+            ("deprecated-badge", "deprecated", schema.deprecated),
+            ("read-only-badge", "read-only", schema.read_only),
+            ("write-only-badge", "write-only", schema.write_only),
+        ];
+        for (class, label, _) in annotations.iter().filter(|(_, _, set)| *set) {
+            html.push_str(&format!(" <span class=\"{}\">{}</span>", class, label));
+        }
+
+        if let Some(origin) = &property.origin {
+            html.push_str(&format!(
+                " <span class=\"property-origin\">from {}</span>",
+                escape_html(origin)
+            ));
+        }
+
+        html.push_str("</div>");
+
+        if let Some(description) = &schema.description {
+            html.push_str(&format!(
+                "<div class=\"property-description\">{}</div>",
+                escape_html(description)
+            ));
+        }
+
+        html.push_str(&self.render_schema_details(schema, depth + 1));
+
+        html.push_str("</div>");
+
+        html
+    }
+
+    fn render_schema_details(&self, schema: &Schema, depth: usize) -> String {
+        let mut html = String::new();
+
+        html.push_str(&format!("<div class=\"schema-details depth-{}\">", depth));
+
+        if let Some(boolean) = schema.boolean {
+            html.push_str(&format!(
+                "<span class=\"boolean-schema\">{}</span></div>",
+                if boolean { "allowed" } else { "not allowed" }
+            ));
+            return html;
+        }
+
+        if let Some(reference) = &schema.reference {
+            html.push_str(&render_ref_link(reference));
+        }
+
+        // Constraints
+        if !schema.constraints.is_empty() {
+            html.push_str("<div class=\"constraints\">");
+            for constraint in &schema.constraints {
+                html.push_str(&format!(
+                    "<span class=\"constraint\">{}</span>",
+                    escape_html(&constraint.to_string())
+                ));
+            }
+            html.push_str("</div>");
+        }
+
+        // Enum values
+        if let Some(enum_values) = &schema.enum_values {
+            html.push_str("<div class=\"enum-values\">");
+            html.push_str("<span class=\"enum-label\">Possible values:</span>");
+            for value in enum_values {
+                html.push_str(&format!(
+                    " <span class=\"enum-value\">{}</span>",
+                    escape_html(&format_json_value(value))
+                ));
+            }
+            html.push_str("</div>");
+        }
+
+        // Default value
+        if let Some(default) = &schema.default {
+            html.push_str(&format!(
+                "<div class=\"default-value\">Default: <code>{}</code></div>",
+                escape_html(&format_json_value(default))
+            ));
+        }
+
+        // Examples
+        if !schema.examples.is_empty() {
+            html.push_str("<div class=\"examples\">");
+            html.push_str("<span class=\"examples-label\">Examples:</span>");
+            for example in &schema.examples {
+                let ex_str = match example {
+                    Value::String(s) => s.clone(),
+                    v => v.to_string(),
+                };
+                html.push_str(&format!(" <code>{}</code>", escape_html(&ex_str)));
+            }
+            html.push_str("</div>");
+        }
+
+        // Nested properties for objects
+        if !schema.properties.is_empty() {
+            html.push_str("<div class=\"nested-properties\">");
+            for property in &schema.properties {
+                html.push_str(&self.render_property(property, depth));
+            }
+            html.push_str("</div>");
+        }
+
+        // Array items
+        if let Some(items) = &schema.items {
+            html.push_str("<div class=\"array-items\">");
+            html.push_str("<div class=\"array-label\">Items:</div>");
+            html.push_str(&self.render_schema_details(items, depth));
+            html.push_str("</div>");
+        }
+
+        html.push_str(&self.render_applicators(&schema.applicators, depth));
+
+        if let Some(conditional) = &schema.conditional {
+            html.push_str(&self.render_conditional(conditional, depth));
+        }
+
+        for compound in &schema.compounds {
+            // Numbered in document order, so every tab group has its own ids
+            let group = self.compounds.get() + 1;
+            self.compounds.set(group);
+            let id_prefix = format!("{}-", group);
+            html.push_str(&self.render_compound(compound, 4, depth, &id_prefix));
+        }
+
+        html.push_str("</div>");
+
+        html
+    }
+
+    /// Type badge and details of an applicator's subschema
+    fn render_subschema(&self, schema: &Schema, depth: usize) -> String {
+        let mut html = String::new();
+        if let Some(schema_type) = &schema.schema_type {
+            html.push_str(&format!(
+                "<span class=\"type-badge\">{}</span>",
+                escape_html(schema_type)
+            ));
+        }
+        html.push_str(&self.render_schema_details(schema, depth));
+        html
+    }
+
+    fn render_applicators(&self, applicators: &Applicators, depth: usize) -> String {
+        let mut html = String::new();
+
+        if !applicators.prefix_items.is_empty() {
+            html.push_str(&applicator_start("prefix-items", "Prefix items"));
+            for (i, item) in applicators.prefix_items.iter().enumerate() {
+                html.push_str(&format!(
+                    "<div class=\"prefix-item\"><div class=\"item-index\">[{}]</div>",
+                    i
+                ));
+                html.push_str(&self.render_subschema(item, depth));
+                html.push_str("</div>");
+            }
+            html.push_str("</div>");
+        }
+
+        if let Some(contains) = &applicators.contains {
+            html.push_str(&applicator_start("contains", "Contains"));
+            let bounds = [("minContains", contains.min), ("maxContains", contains.max)];
+            if bounds.iter().any(|(_, bound)| bound.is_some()) {
+                html.push_str("<div class=\"constraints\">");
+                for (label, bound) in bounds {
+                    if let Some(bound) = bound {
+                        html.push_str(&format!(
+                            "<span class=\"constraint\">{}: {}</span>",
+                            label, bound
+                        ));
+                    }
+                }
+                html.push_str("</div>");
+            }
+            html.push_str(&self.render_subschema(&contains.schema, depth));
+            html.push_str("</div>");
+        }
+
+        if !applicators.pattern_properties.is_empty() {
+            html.push_str(&applicator_start(
+                "pattern-properties",
+                "Pattern properties",
+            ));
+            for pattern in &applicators.pattern_properties {
+                html.push_str(&format!(
+                    "<div class=\"pattern-property\"><code class=\"property-pattern\">{}</code>",
+                    escape_html(&pattern.pattern)
+                ));
+                html.push_str(&self.render_subschema(&pattern.schema, depth));
+                html.push_str("</div>");
+            }
+            html.push_str("</div>");
+        }
+
+        let subschemas = [
+            (
+                "additional-properties",
+                "Additional properties",
+                &applicators.additional_properties,
+            ),
+            (
+                "unevaluated-properties",
+                "Unevaluated properties",
+                &applicators.unevaluated_properties,
+            ),
+            (
+                "property-names",
+                "Property names",
+                &applicators.property_names,
+            ),
+            ("not-schema", "Not", &applicators.not),
+        ];
+        for (class, label, subschema) in subschemas {
+            if let Some(subschema) = subschema {
+                html.push_str(&applicator_start(class, label));
+                html.push_str(&self.render_subschema(subschema, depth));
+                html.push_str("</div>");
+            }
+        }
+
+        if !applicators.dependent_required.is_empty() {
+            html.push_str(&applicator_start(
+                "dependent-required",
+                "Dependent required",
+            ));
+            for dependency in &applicators.dependent_required {
+                html.push_str(&format!(
+                    "<div class=\"dependency\"><code class=\"dependency-property\">{}</code><span class=\"dependency-label\">requires</span>",
+                    escape_html(&dependency.property)
+                ));
+                for required in &dependency.required {
+                    html.push_str(&format!(" <code>{}</code>", escape_html(required)));
+                }
+                html.push_str("</div>");
+            }
+            html.push_str("</div>");
+        }
+
+        if !applicators.dependent_schemas.is_empty() {
+            html.push_str(&applicator_start("dependent-schemas", "Dependent schemas"));
+            for dependency in &applicators.dependent_schemas {
+                html.push_str(&format!(
+                    "<div class=\"dependency\"><code class=\"dependency-property\">{}</code><span class=\"dependency-label\">is present</span>",
+                    escape_html(&dependency.property)
+                ));
+                html.push_str(&self.render_subschema(&dependency.schema, depth));
+                html.push_str("</div>");
+            }
+            html.push_str("</div>");
+        }
+
+        html
+    }
+
+    /// The `if` schema and its branches, whose properties render like any
+    /// other object's
+    fn render_conditional(&self, conditional: &Conditional, depth: usize) -> String {
+        let mut html = String::from("<div class=\"conditional\">");
+
+        let branches = [
+            ("condition", "If", Some(&conditional.condition)),
+            ("then-branch", "Then", conditional.then_branch.as_ref()),
+            ("else-branch", "Else", conditional.else_branch.as_ref()),
+        ];
+        for (class, label, branch) in branches {
+            if let Some(branch) = branch {
+                html.push_str(&format!(
+                    "<div class=\"{}\"><div class=\"conditional-label\">{}:</div>",
+                    class, label
+                ));
+                html.push_str(&self.render_subschema(branch, depth));
+                html.push_str("</div>");
+            }
+        }
+
+        html.push_str("</div>");
+
+        html
+    }
+
+    /// Options of a compound under an `<h{heading}>` with their details at
+    /// `depth`, `id_prefix` keeps the tab ids of different compounds of a
+    /// kind apart
+    fn render_compound(
+        &self,
+        compound: &Compound,
+        heading: usize,
+        depth: usize,
+        id_prefix: &str,
+    ) -> String {
+        let mut html = String::new();
+
+        html.push_str(&format!(
+            "<div class=\"compound-schema\"><h{0}>{1}</h{0}>",
+            heading,
+            compound.kind.label()
+        ));
+        if let Some(discriminator) = &compound.discriminator {
+            html.push_str(&format!(
+                "<div class=\"discriminator\"><span class=\"discriminator-label\">Discriminator:</span><code>{}</code></div>",
+                escape_html(discriminator)
+            ));
+        }
+
+        if self.renderer.tabs {
+            html.push_str("<div class=\"compound-options compound-tabs\">");
+        } else {
+            html.push_str("<div class=\"compound-options\">");
+        }
+
+        for (i, option) in compound.options.iter().enumerate() {
+            let label = match &option.label {
+                Some(label) => escape_html(label),
+                None => format!("Option {}", i + 1),
+            };
+            if self.renderer.tabs {
+                // Radio buttons switch between the options without scripts
+                let id = format!("tab-{}{}-{}", id_prefix, compound.kind.keyword(), i + 1);
+                html.push_str(&format!(
+                    "<input type=\"radio\" class=\"tab-input\" name=\"tab-{}{}\" id=\"{}\"{}>",
+                    id_prefix,
+                    compound.kind.keyword(),
+                    id,
+                    if i == 0 { " checked" } else { "" }
+                ));
+                html.push_str(&format!(
+                    "<label class=\"tab-label\" for=\"{}\">{}</label>",
+                    id, label
+                ));
+                html.push_str("<div class=\"compound-option tab-panel\">");
+            } else {
+                html.push_str(&format!(
+                    "<div class=\"compound-option\"><h{0}>{1}</h{0}>",
+                    heading + 1,
+                    label
+                ));
+            }
+            html.push_str(&self.render_schema_details(&option.schema, depth));
+            html.push_str("</div>");
+        }
+
+        html.push_str("</div></div>");
+
+        html
+    }
+
+    fn render_definition(&self, definition: &Definition) -> String {
+        format!(
+            "<div class=\"definition\" id=\"def-{0}\"><h3>{0}</h3>{1}</div>",
+            escape_html(&definition.name),
+            self.render_schema_details(&definition.schema, 0)
+        )
+    }
+}
+
+/// Opening tag and label of an applicator block
+fn applicator_start(class: &str, label: &str) -> String {
+    format!(
+        "<div class=\"{}\"><div class=\"applicator-label\">{}:</div>",
+        class, label
+    )
+}
+
+fn render_ref_link(reference: &Reference) -> String {
+    let (class, label) = if reference.recursive {
+        ("schema-ref recursive-ref", "recursive: see")
+    } else {
+        ("schema-ref", "See")
+    };
+    let target = match (&reference.href, &reference.definition) {
+        (Some(href), name) => format!(
+            "<a class=\"ref-link\" href=\"{}\">{}</a>",
+            escape_html(href),
+            escape_html(name.as_deref().unwrap_or(&reference.text))
+        ),
+        (None, Some(name)) => format!(
+            "<a class=\"ref-link\" href=\"#def-{0}\">{0}</a>",
+            escape_html(name)
+        ),
+        (None, None) => format!("<code>{}</code>", escape_html(&reference.text)),
+    };
+
+    format!(
+        "<div class=\"{}\"><span class=\"ref-label\">{}</span> {}</div>",
+        class, label, target
+    )
+}
+
 /// HTML minification functionality
 pub fn minify(html: &str) -> String {
     let mut result = String::new();
@@ -5,21 +621,34 @@ pub fn minify(html: &str) -> String {
     let mut in_tag = false;
     let mut in_quotes = false;
     let mut quote_char = ' ';
+    // Whitespace inside `<pre>` is kept as is
+    let mut in_pre = false;
+    let mut tag_start = 0;
 
     for ch in html.chars() {
         match ch {
             '<' if !in_quotes => {
                 in_tag = true;
                 // Remove whitespace before tag
-                if !result.is_empty() && prev_char.is_whitespace() {
+                if !in_pre && !result.is_empty() && prev_char.is_whitespace() {
                     let trimmed_len = result.trim_end().len();
                     result.truncate(trimmed_len);
                 }
+                tag_start = result.len();
                 result.push(ch);
             }
             '>' if !in_quotes => {
                 in_tag = false;
                 result.push(ch);
+                let name = result[tag_start + 1..]
+                    .split(|c: char| c.is_whitespace() || c == '>')
+                    .next()
+                    .unwrap_or_default();
+                match name {
+                    "pre" => in_pre = true,
+                    "/pre" => in_pre = false,
+                    _ => {}
+                }
             }
             '"' | '\'' if in_tag => {
                 if in_quotes && ch == quote_char {
@@ -30,7 +659,7 @@ pub fn minify(html: &str) -> String {
                 }
                 result.push(ch);
             }
-            ' ' | '\t' | '\n' | '\r' if !in_quotes => {
+            ' ' | '\t' | '\n' | '\r' if !in_quotes && !in_pre => {
                 // Collapse multiple whitespaces to single space
                 if !prev_char.is_whitespace() {
                     result.push(' ');
@@ -50,6 +679,7 @@ pub fn minify(html: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::json_schema;
 
     #[test]
     fn test_basic_minification() {
@@ -87,6 +717,16 @@ mod tests {
         assert_eq!(minified, "<div><span>Text</span><p>More</p></div>");
     }
 
+    #[test]
+    fn test_preserve_whitespace_in_pre() {
+        let html = "<div>\n  <pre class=\"example\"><code>{\n  \"a\": 1\n}</code></pre>\n</div>";
+        let minified = minify(html);
+        assert_eq!(
+            minified,
+            "<div><pre class=\"example\"><code>{\n  \"a\": 1\n}</code></pre></div>"
+        );
+    }
+
     #[test]
     fn test_single_quotes() {
         let html = r#"<div class='my class' data-value='test  value'>Content</div>"#;
@@ -120,6 +760,76 @@ mod tests {
         assert_eq!(minify(html), "Just some text without tags");
     }
 
+    #[test]
+    fn test_document_default_stylesheet() {
+        let html = document(
+            Some("Device <Config>"),
+            "<div class=\"schema-container\"></div>",
+            &Stylesheet::Default,
+        );
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Device &lt;Config&gt;</title>"));
+        assert!(html.contains("<style>"));
+        assert!(html.contains(".schema-container {"));
+        assert!(html.contains("<body>\n<div class=\"schema-container\"></div>\n</body>"));
+    }
+
+    #[test]
+    fn test_document_linked_stylesheet() {
+        let html = document(None, "", &Stylesheet::Link("css/docs.css".to_string()));
+
+        assert!(html.contains("<title>Schema Documentation</title>"));
+        assert!(html.contains(r#"<link rel="stylesheet" href="css/docs.css">"#));
+        assert!(!html.contains("<style>"));
+    }
+
+    #[test]
+    fn test_document_inline_stylesheet() {
+        let html = document(
+            None,
+            "",
+            &Stylesheet::Inline("body { color: red; }".to_string()),
+        );
+
+        assert!(html.contains("<style>\nbody { color: red; }</style>"));
+        assert!(!html.contains(".schema-container {"));
+    }
+
+    #[test]
+    fn test_default_stylesheet_covers_documented_classes() {
+        let docs = include_str!("../docs/css-classes.md");
+
+        for line in docs.lines() {
+            let Some(class) = line
+                .strip_prefix("### `.")
+                .and_then(|rest| rest.strip_suffix('`'))
+            else {
+                continue;
+            };
+            assert!(
+                DEFAULT_STYLESHEET.contains(&format!(".{}", class)),
+                "default stylesheet is missing .{}",
+                class
+            );
+        }
+    }
+
+    #[test]
+    fn test_generate_html() {
+        let schema = serde_json::json!({
+            "title": "Light",
+            "properties": {"on": {"type": "boolean"}}
+        });
+        let options = json_schema::Options::default();
+
+        let html = json_schema::generate_html(&schema, &options).unwrap();
+        assert!(html.starts_with(r#"<div class="schema-container">"#));
+        assert!(html.contains(r#"<span class="property-name">on</span>"#));
+        let document = json_schema::build_document(&schema, &options).unwrap();
+        assert_eq!(html, HtmlRenderer::default().render(&document).unwrap());
+    }
+
     #[test]
     fn test_self_closing_tags() {
         let html = r#"<img src="test.jpg" />  <br />  <hr />"#;
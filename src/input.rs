@@ -0,0 +1,189 @@
+//! Reading schema documents in the supported input formats.
+
+use clap::ValueEnum;
+use serde::Deserialize;
+use serde_json::Value;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use tracing::debug;
+
+use crate::error::{Error, Result};
+
+/// Format of a schema document
+#[derive(ValueEnum, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum InputFormat {
+    Json,
+    Yaml,
+}
+
+impl InputFormat {
+    /// Format implied by a file extension, if it's a known one.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            "json" => Some(InputFormat::Json),
+            "yaml" | "yml" => Some(InputFormat::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Guess the format from the document content.
+    ///
+    /// Anything that doesn't clearly look like YAML is treated as JSON, so
+    /// malformed JSON is still reported as a JSON error.
+    pub fn detect(content: &str) -> Self {
+        let first_line = content
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or("");
+
+        if first_line.starts_with('{') || first_line.starts_with('[') {
+            InputFormat::Json
+        } else if first_line.starts_with("---")
+            || first_line.starts_with('%')
+            || first_line.starts_with('#')
+            || first_line.ends_with(':')
+            || first_line.contains(": ")
+        {
+            InputFormat::Yaml
+        } else {
+            InputFormat::Json
+        }
+    }
+}
+
+impl std::fmt::Display for InputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            InputFormat::Json => "JSON",
+            InputFormat::Yaml => "YAML",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Parse a document in the given format.
+pub fn parse(content: &str, format: InputFormat) -> Result<Value> {
+    match format {
+        InputFormat::Json => Ok(serde_json::from_str(content)?),
+        InputFormat::Yaml => Ok(serde_yaml::from_str(content)?),
+    }
+}
+
+/// Read and parse the document at `path`.
+///
+/// The format is taken from `format`, then the file extension, and is
+/// otherwise detected from the content.
+pub fn read(path: &Path, format: Option<InputFormat>) -> Result<Value> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| Error::Other(format!("Failed to read '{}': {}", path.display(), e)))?;
+
+    let format = format
+        .or_else(|| InputFormat::from_path(path))
+        .unwrap_or_else(|| InputFormat::detect(&content));
+
+    let parsed = match format {
+        InputFormat::Json => serde_json::from_str(&content).map_err(|e| e.to_string()),
+        InputFormat::Yaml => serde_yaml::from_str(&content).map_err(|e| e.to_string()),
+    };
+
+    parsed.map_err(|e| {
+        Error::Other(format!(
+            "Failed to parse {} from '{}': {}",
+            format,
+            path.display(),
+            e
+        ))
+    })
+}
+
+/// Read the schema from `path`, or from stdin without one.
+///
+/// Returns a name for the source to use in messages along with the schema.
+pub fn load(path: Option<&Path>, format: Option<InputFormat>) -> Result<(String, Value)> {
+    if let Some(path) = path {
+        debug!("Reading schema from: {}", path.display());
+
+        if !path.exists() {
+            return Err(Error::Other(format!(
+                "Input file '{}' not found.",
+                path.display()
+            )));
+        }
+
+        return Ok((path.display().to_string(), read(path, format)?));
+    }
+
+    debug!("No input file specified, reading from stdin.");
+    let mut buffer = String::new();
+    io::stdin().read_to_string(&mut buffer).map_err(Error::Io)?;
+
+    let format = format.unwrap_or_else(|| InputFormat::detect(&buffer));
+    debug!("Parsing stdin as {}.", format);
+
+    Ok(("stdin".to_string(), parse(&buffer, format)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(
+            InputFormat::from_path(Path::new("schema.json")),
+            Some(InputFormat::Json)
+        );
+        assert_eq!(
+            InputFormat::from_path(Path::new("schema.YAML")),
+            Some(InputFormat::Yaml)
+        );
+        assert_eq!(
+            InputFormat::from_path(Path::new("schema.yml")),
+            Some(InputFormat::Yaml)
+        );
+        assert_eq!(InputFormat::from_path(Path::new("schema")), None);
+        assert_eq!(InputFormat::from_path(Path::new("schema.txt")), None);
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(
+            InputFormat::detect(r#"{"type":"object"}"#),
+            InputFormat::Json
+        );
+        assert_eq!(InputFormat::detect("\n  [1, 2]"), InputFormat::Json);
+        assert_eq!(InputFormat::detect("type: object"), InputFormat::Yaml);
+        assert_eq!(InputFormat::detect("---\ntype: object"), InputFormat::Yaml);
+        assert_eq!(InputFormat::detect("# comment\n"), InputFormat::Yaml);
+        assert_eq!(InputFormat::detect("properties:\n"), InputFormat::Yaml);
+        assert_eq!(InputFormat::detect("not valid json"), InputFormat::Json);
+        assert_eq!(InputFormat::detect(""), InputFormat::Json);
+    }
+
+    #[test]
+    fn test_parse_yaml() {
+        let value = parse(
+            "type: object\nproperties:\n  name:\n    type: string\n",
+            InputFormat::Yaml,
+        )
+        .unwrap();
+
+        assert_eq!(
+            value,
+            json!({"type": "object", "properties": {"name": {"type": "string"}}})
+        );
+    }
+
+    #[test]
+    fn test_parse_yaml_error_location() {
+        let err = parse("type: object\nproperties: [\n", InputFormat::Yaml).unwrap_err();
+
+        assert!(err.to_string().starts_with("YAML error:"));
+        assert!(err.to_string().contains("line 3 column 1"));
+    }
+}
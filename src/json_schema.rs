@@ -1,277 +1,804 @@
 use serde_json::Value;
-use std::collections::HashSet;
-
-/// Main function to generate HTML documentation from a JSON Schema
-pub fn generate_html(schema: &Value) -> Result<String, crate::error::Error> {
-    let mut html = String::new();
-
-    // Main container
-    html.push_str("<div class=\"schema-container\">");
-
-    // Schema header
-    html.push_str(&generate_header(schema));
-
-    // Generate property documentation
-    if schema.get("type").and_then(|v| v.as_str()) == Some("object") {
-        if let Some(properties) = schema.get("properties").and_then(|v| v.as_object()) {
-            html.push_str("<div class=\"properties-section\">");
-            html.push_str("<h2>Properties</h2>");
-
-            let required = get_required_fields(schema);
-
-            html.push_str("<div class=\"properties-list\">");
-            for (prop_name, prop_schema) in properties {
-                let is_required = required.contains(prop_name.as_str());
-                html.push_str(&generate_property_html(
-                    prop_name,
-                    prop_schema,
-                    is_required,
-                    0,
-                )?);
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use tracing::{debug, warn};
+
+use crate::document::{
+    Applicators, Compound, CompoundKind, CompoundOption, Conditional, Constraint, Contains,
+    Definition, DependentRequired, DependentSchema, Document, PatternProperty, Property, Reference,
+    Schema, Section,
+};
+use crate::example;
+use crate::html::HtmlRenderer;
+use crate::render::Renderer;
+use crate::resolver::{self, Resolver, Scope};
+
+/// Default limit on how many `$ref`s are expanded inside each other
+pub const DEFAULT_MAX_DEPTH: usize = 8;
+
+/// Keywords that only constrain objects, implying `object` when `type` is
+/// omitted
+const OBJECT_KEYWORDS: &[&str] = &[
+    "additionalProperties",
+    "dependentRequired",
+    "dependentSchemas",
+    "maxProperties",
+    "minProperties",
+    "patternProperties",
+    "properties",
+    "propertyNames",
+    "required",
+    "unevaluatedProperties",
+];
+
+/// Keywords that only constrain arrays, implying `array` when `type` is
+/// omitted
+const ARRAY_KEYWORDS: &[&str] = &[
+    "additionalItems",
+    "contains",
+    "items",
+    "maxContains",
+    "maxItems",
+    "minContains",
+    "minItems",
+    "prefixItems",
+    "unevaluatedItems",
+    "uniqueItems",
+];
+
+/// Options controlling documentation generation
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Path of the schema file, relative `$ref`s are resolved against it
+    /// (defaults to the current directory)
+    pub path: Option<PathBuf>,
+    /// Maximum number of nested `$ref` expansions, deeper references are
+    /// only linked
+    pub max_depth: usize,
+    /// Document the members of `allOf` as one schema instead of as options
+    pub merge_all_of: bool,
+    /// Add an example document generated from the schema
+    pub example: bool,
+    /// Pages other schema files are documented on, keyed by their absolute
+    /// path. References into them link to the page instead of expanding.
+    pub pages: HashMap<PathBuf, String>,
+    /// JSON Pointer to the part of the document to render, `$ref`s still
+    /// resolve against the whole document
+    pub pointer: Option<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            path: None,
+            max_depth: DEFAULT_MAX_DEPTH,
+            merge_all_of: false,
+            example: false,
+            pages: HashMap::new(),
+            pointer: None,
+        }
+    }
+}
+
+/// State threaded through the schema traversal
+struct Context {
+    resolver: Resolver,
+    /// Schemas currently being documented, innermost last
+    frames: Vec<Frame>,
+    /// Canonical ids of the schemas currently being expanded, a reference
+    /// to any of them is recursive
+    expanding: Vec<String>,
+    /// Number of `$ref`s currently being expanded
+    ref_depth: usize,
+    max_depth: usize,
+    merge_all_of: bool,
+    pages: HashMap<PathBuf, String>,
+    /// Whether the root definitions are documented, so references to them
+    /// can link to their section
+    definitions: bool,
+}
+
+struct Frame {
+    scope: Scope,
+    /// Whether entering this schema expanded a `$ref`
+    expanded_ref: bool,
+}
+
+/// A schema being documented, after following its `$ref`
+struct Entered<'v> {
+    /// The schema, or its merged target for references
+    schema: Cow<'v, Value>,
+    reference: Option<Reference>,
+}
+
+impl Context {
+    /// Context for documenting the schema at `pointer` in `schema`
+    fn new(schema: &Value, pointer: &str, options: &Options) -> Result<Self, crate::error::Error> {
+        let location = resolver::document_location(options.path.as_deref())?;
+        let resolver = Resolver::new(schema, location.clone());
+
+        Ok(Self {
+            frames: vec![Frame {
+                scope: resolver::scope_at(schema, &location, pointer),
+                expanded_ref: false,
+            }],
+            resolver,
+            // References back to the documented schema are recursive too
+            expanding: vec![format!("{}#{}", location, pointer)],
+            ref_depth: 0,
+            max_depth: options.max_depth,
+            merge_all_of: options.merge_all_of,
+            pages: options.pages.clone(),
+            definitions: pointer.is_empty(),
+        })
+    }
+
+    fn scope(&self) -> &Scope {
+        &self
+            .frames
+            .last()
+            .expect("traversal always has a root frame")
+            .scope
+    }
+
+    /// The root schema to document, following a root `$ref`
+    ///
+    /// The target is being documented from then on, so references back to
+    /// it are recursive.
+    fn root<'v>(&mut self, schema: &'v Value) -> Cow<'v, Value> {
+        let Some(reference) = schema.get("$ref").and_then(|v| v.as_str()) else {
+            return Cow::Borrowed(schema);
+        };
+        match self.resolver.resolve(self.scope(), reference) {
+            Some(target) => {
+                self.expanding.push(target.id());
+                Cow::Owned(resolver::merge(schema, target.schema))
             }
-            html.push_str("</div>");
-            html.push_str("</div>");
+            None => Cow::Borrowed(schema),
         }
     }
 
-    // Handle array schemas
-    if schema.get("type").and_then(|v| v.as_str()) == Some("array") {
-        if let Some(items) = schema.get("items") {
-            html.push_str("<div class=\"array-section\">");
-            html.push_str("<h2>Array Items</h2>");
-            html.push_str(&generate_schema_details(items, 0)?);
-            html.push_str("</div>");
+    /// Start documenting `schema`, every call must be paired with `leave`
+    fn enter<'v>(&mut self, schema: &'v Value) -> Entered<'v> {
+        let scope = self.scope().enter(schema);
+        let Some(text) = schema.get("$ref").and_then(|v| v.as_str()) else {
+            self.frames.push(Frame {
+                scope,
+                expanded_ref: false,
+            });
+            return Entered {
+                schema: Cow::Borrowed(schema),
+                reference: None,
+            };
+        };
+
+        let Some(target) = self.resolver.resolve(&scope, text) else {
+            warn!("Unable to resolve reference: {}", text);
+            self.frames.push(Frame {
+                scope,
+                expanded_ref: false,
+            });
+            return Entered {
+                schema: Cow::Borrowed(schema),
+                reference: Some(Reference {
+                    text: text.to_string(),
+                    definition: None,
+                    href: None,
+                    recursive: false,
+                    // Sibling keywords are still documented
+                    expanded: true,
+                }),
+            };
+        };
+
+        let id = target.id();
+        let recursive = self.expanding.contains(&id);
+        let page = self.page(&target);
+        let definition = match page {
+            Some(_) => resolver::definition_name(&target.pointer),
+            None if self.definitions => self.resolver.definition_name(&target),
+            None => None,
+        };
+        let href = page.map(|page| match &definition {
+            Some(name) => format!("{}#def-{}", page, name),
+            None => page.to_string(),
+        });
+        let expand = if href.is_some() {
+            debug!("Linking to the page of: {}", text);
+            false
+        } else if recursive {
+            debug!("Not expanding recursive reference: {}", text);
+            false
+        } else if self.ref_depth >= self.max_depth {
+            debug!("Maximum depth reached, not expanding: {}", text);
+            false
+        } else {
+            true
+        };
+
+        if expand {
+            self.expanding.push(id);
+            self.ref_depth += 1;
+        }
+        self.frames.push(Frame {
+            scope: if expand { target.scope } else { scope },
+            expanded_ref: expand,
+        });
+
+        Entered {
+            schema: Cow::Owned(resolver::merge(schema, target.schema)),
+            reference: Some(Reference {
+                text: text.to_string(),
+                definition,
+                href,
+                recursive,
+                expanded: expand,
+            }),
         }
     }
 
-    // Handle oneOf, anyOf, allOf
-    if let Some(one_of) = schema.get("oneOf").and_then(|v| v.as_array()) {
-        html.push_str(&generate_compound_schema("One Of", one_of)?);
+    /// Page a reference target in another document is documented on
+    fn page(&self, target: &resolver::Resolved) -> Option<&str> {
+        if target.location == self.frames[0].scope.location {
+            return None;
+        }
+        let file = target.location.to_file_path().ok()?;
+        self.pages.get(&file).map(String::as_str)
     }
-    if let Some(any_of) = schema.get("anyOf").and_then(|v| v.as_array()) {
-        html.push_str(&generate_compound_schema("Any Of", any_of)?);
+
+    /// Finish documenting the schema passed to the last `enter`
+    fn leave(&mut self) {
+        let frame = self.frames.pop().expect("leave is paired with enter");
+        if frame.expanded_ref {
+            self.expanding.pop();
+            self.ref_depth -= 1;
+        }
     }
-    if let Some(all_of) = schema.get("allOf").and_then(|v| v.as_array()) {
-        html.push_str(&generate_compound_schema("All Of", all_of)?);
+
+    /// Start documenting a root definition, so references to it are
+    /// recursive, paired with `leave_definition`
+    fn enter_definition(&mut self, keyword: &str, name: &str) {
+        let root = &self.frames[0].scope.location;
+        let id = format!("{}#/{}/{}", root, keyword, resolver::escape_token(name));
+        self.expanding.push(id);
     }
 
-    // Handle definitions/$defs
-    if let Some(definitions) = schema
-        .get("definitions")
-        .or_else(|| schema.get("$defs"))
-        .and_then(|v| v.as_object())
-    {
-        html.push_str("<div class=\"definitions-section\">");
-        html.push_str("<h2>Definitions</h2>");
-        for (def_name, def_schema) in definitions {
-            html.push_str(&format!(
-                "<div class=\"definition\" id=\"def-{}\">",
-                escape_html(def_name)
-            ));
-            html.push_str(&format!("<h3>{}</h3>", escape_html(def_name)));
-            html.push_str(&generate_schema_details(def_schema, 0)?);
-            html.push_str("</div>");
-        }
-        html.push_str("</div>");
+    fn leave_definition(&mut self) {
+        self.expanding.pop();
     }
+}
 
-    html.push_str("</div>");
+/// Root definitions of a schema with the pointer to them, OpenAPI
+/// documents keep theirs under `components/schemas`
+fn get_definitions(schema: &Value) -> Option<(&'static str, &serde_json::Map<String, Value>)> {
+    [
+        ("definitions", schema.get("definitions")),
+        ("$defs", schema.get("$defs")),
+        (
+            "components/schemas",
+            schema.get("components").and_then(|c| c.get("schemas")),
+        ),
+    ]
+    .into_iter()
+    .find_map(|(keyword, defs)| Some((keyword, defs?.as_object()?)))
+}
 
-    Ok(html)
+/// The OpenAPI version of an OpenAPI 3.x document
+pub fn openapi_version(document: &Value) -> Option<&str> {
+    document
+        .get("openapi")
+        .and_then(|v| v.as_str())
+        .filter(|version| version.starts_with("3."))
 }
 
-fn generate_header(schema: &Value) -> String {
-    let mut html = String::new();
+/// Walk a JSON Schema into the documentation model
+///
+/// OpenAPI 3.x documents have no schema of their own, their
+/// `components/schemas` are documented as definitions.
+pub fn build_document(schema: &Value, options: &Options) -> Result<Document, crate::error::Error> {
+    let (selected, pointer) = resolver::select(schema, options.pointer.as_deref().unwrap_or(""))?;
+    let pointer = pointer.as_str();
+    let mut ctx = Context::new(schema, pointer, options)?;
+    let openapi = pointer.is_empty() && openapi_version(schema).is_some();
+
+    let mut document = if openapi {
+        let info = schema.get("info").unwrap_or(&Value::Null);
+        Document {
+            title: get_string(info, "title"),
+            description: get_string(info, "description"),
+            sections: Vec::new(),
+        }
+    } else {
+        Document {
+            // A selected definition is known by its name
+            title: get_string(selected, "title").or_else(|| resolver::definition_name(pointer)),
+            description: get_string(selected, "description"),
+            sections: Vec::new(),
+        }
+    };
+
+    // A root `$ref` documents its target, definitions stay on the root
+    let root = ctx.root(selected);
+    let root = root.as_ref();
+
+    let mut properties = None;
+    if allows_type(root, "object", OBJECT_KEYWORDS) {
+        if let Some(root_properties) = root.get("properties").and_then(|v| v.as_object()) {
+            properties = Some(build_properties(root, root_properties, &mut ctx));
+        }
+    }
+
+    // Merged `allOf` members are listed with the root's own properties
+    let merge_all_of = ctx.merge_all_of && root.get("allOf").is_some();
+    if merge_all_of {
+        let mut merged = Schema {
+            properties: properties.unwrap_or_default(),
+            ..Schema::default()
+        };
+        self::merge_all_of(root, &mut merged, &mut ctx);
+        properties = Some(merged.properties);
+    }
+
+    if let Some(properties) = properties {
+        document.sections.push(Section::Properties(properties));
+    }
 
-    html.push_str("<div class=\"schema-header\">");
+    if allows_type(root, "array", ARRAY_KEYWORDS) {
+        if let Some(items) = root.get("items") {
+            document
+                .sections
+                .push(Section::ArrayItems(Box::new(build_schema(items, &mut ctx))));
+        }
+    }
+
+    let applicators = build_applicators(root, &mut ctx);
+    if !applicators.is_empty() {
+        document
+            .sections
+            .push(Section::Applicators(Box::new(applicators)));
+    }
+
+    if let Some(conditional) = build_conditional(root, &mut ctx) {
+        document
+            .sections
+            .push(Section::Conditional(Box::new(conditional)));
+    }
+
+    for compound in build_compounds(root, merge_all_of, &mut ctx) {
+        document.sections.push(Section::Compound(compound));
+    }
+
+    let definitions = if ctx.definitions {
+        get_definitions(schema)
+    } else {
+        None
+    };
+    if let Some((keyword, definitions)) = definitions {
+        let mut entries = Vec::new();
+        for (def_name, def_schema) in definitions {
+            // A definition referencing itself links back here instead of expanding
+            ctx.enter_definition(keyword, def_name);
+            let schema = build_schema(def_schema, &mut ctx);
+            entries.push(Definition {
+                name: def_name.clone(),
+                schema,
+            });
+            ctx.leave_definition();
+        }
+        document.sections.push(Section::Definitions(entries));
+    }
 
-    // Only include description if present, no title
-    if let Some(description) = schema.get("description").and_then(|v| v.as_str()) {
-        html.push_str(&format!(
-            "<p class=\"schema-description\">{}</p>",
-            escape_html(description)
-        ));
+    if options.example && openapi {
+        warn!("OpenAPI documents get no example, select a schema with a pointer.");
+    } else if options.example {
+        let options = example::Options {
+            path: options.path.clone(),
+            pointer: Some(pointer.to_string()),
+            required_only: false,
+        };
+        document
+            .sections
+            .push(Section::Example(example::generate(schema, &options)?));
     }
 
-    html.push_str("</div>");
+    Ok(document)
+}
 
-    html
+/// Generate HTML documentation markup from a JSON Schema
+///
+/// Shorthand for [`build_document`] rendered by the default [`HtmlRenderer`],
+/// use those directly for other output formats and renderer options.
+// Library API, the CLI renders through `Renderer` itself
+#[allow(dead_code)]
+pub fn generate_html(schema: &Value, options: &Options) -> Result<String, crate::error::Error> {
+    HtmlRenderer::default().render(&build_document(schema, options)?)
 }
 
-fn generate_property_html(
-    name: &str,
+fn build_properties(
     schema: &Value,
-    required: bool,
-    depth: usize,
-) -> Result<String, crate::error::Error> {
-    let mut html = String::new();
+    properties: &serde_json::Map<String, Value>,
+    ctx: &mut Context,
+) -> Vec<Property> {
+    let required = get_required_fields(schema);
+
+    properties
+        .iter()
+        .map(|(prop_name, prop_schema)| Property {
+            name: prop_name.clone(),
+            required: required.contains(prop_name.as_str()),
+            schema: build_schema(prop_schema, ctx),
+            origin: None,
+        })
+        .collect()
+}
 
-    html.push_str(&format!(
-        "<div class=\"property depth-{}\" data-property=\"{}\">",
-        depth,
-        escape_html(name)
-    ));
+fn build_schema(schema: &Value, ctx: &mut Context) -> Schema {
+    if let Value::Bool(boolean) = schema {
+        return Schema {
+            boolean: Some(*boolean),
+            ..Schema::default()
+        };
+    }
+
+    // References link to their target and document it in place
+    let entered = ctx.enter(schema);
+    let resolved = entered.schema.as_ref();
+    let mut node = Schema {
+        title: get_string(resolved, "title"),
+        schema_type: get_schema_type(resolved),
+        description: get_string(resolved, "description"),
+        reference: entered.reference.clone(),
+        deprecated: get_flag(resolved, "deprecated"),
+        read_only: get_flag(resolved, "readOnly"),
+        write_only: get_flag(resolved, "writeOnly"),
+        ..Schema::default()
+    };
+    if entered.reference.as_ref().is_some_and(|r| !r.expanded) {
+        ctx.leave();
+        return node;
+    }
+
+    node.constraints = get_constraints(resolved);
+    node.enum_values = resolved.get("enum").and_then(|v| v.as_array()).cloned();
+    node.default = resolved.get("default").cloned();
+    node.examples = resolved
+        .get("examples")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    // OpenAPI's single `example`
+    if let Some(example) = resolved.get("example") {
+        node.examples.push(example.clone());
+    }
 
-    html.push_str("<div class=\"property-header\">");
-    html.push_str(&format!(
-        "<span class=\"property-name\">{}</span>",
-        escape_html(name)
-    ));
+    if allows_type(resolved, "object", OBJECT_KEYWORDS) {
+        if let Some(properties) = resolved.get("properties").and_then(|v| v.as_object()) {
+            node.properties = build_properties(resolved, properties, ctx);
+        }
+    }
 
-    if let Some(prop_type) = get_schema_type(schema) {
-        html.push_str(&format!(
-            " <span class=\"type-badge\">{}</span>",
-            escape_html(&prop_type)
-        ));
+    if ctx.merge_all_of {
+        merge_all_of(resolved, &mut node, ctx);
     }
 
-    if required {
-        html.push_str(" <span class=\"required-badge\">required</span>");
+    if allows_type(resolved, "array", ARRAY_KEYWORDS) {
+        if let Some(items) = resolved.get("items") {
+            node.items = Some(Box::new(build_schema(items, ctx)));
+        }
     }
 
-    html.push_str("</div>");
+    node.applicators = build_applicators(resolved, ctx);
+    node.conditional = build_conditional(resolved, ctx);
+    let merge_all_of = ctx.merge_all_of;
+    node.compounds = build_compounds(resolved, merge_all_of, ctx);
 
-    if let Some(description) = schema.get("description").and_then(|v| v.as_str()) {
-        html.push_str(&format!(
-            "<div class=\"property-description\">{}</div>",
-            escape_html(description)
-        ));
-    }
+    ctx.leave();
 
-    html.push_str(&generate_schema_details(schema, depth + 1)?);
+    node
+}
 
-    html.push_str("</div>");
+fn build_applicators(schema: &Value, ctx: &mut Context) -> Applicators {
+    let mut applicators = Applicators::default();
+
+    if let Some(prefix_items) = schema.get("prefixItems").and_then(|v| v.as_array()) {
+        applicators.prefix_items = prefix_items
+            .iter()
+            .map(|item| build_schema(item, ctx))
+            .collect();
+    }
+
+    let mut subschema = |keyword: &str| {
+        schema
+            .get(keyword)
+            .map(|value| Box::new(build_schema(value, ctx)))
+    };
+    applicators.additional_properties = subschema("additionalProperties");
+    applicators.property_names = subschema("propertyNames");
+    applicators.unevaluated_properties = subschema("unevaluatedProperties");
+    applicators.not = subschema("not");
+    applicators.contains = subschema("contains").map(|schema_node| Contains {
+        schema: schema_node,
+        min: schema.get("minContains").and_then(|v| v.as_u64()),
+        max: schema.get("maxContains").and_then(|v| v.as_u64()),
+    });
+
+    if let Some(patterns) = schema.get("patternProperties").and_then(|v| v.as_object()) {
+        applicators.pattern_properties = patterns
+            .iter()
+            .map(|(pattern, value)| PatternProperty {
+                pattern: pattern.clone(),
+                schema: build_schema(value, ctx),
+            })
+            .collect();
+    }
+
+    if let Some(dependencies) = schema.get("dependentRequired").and_then(|v| v.as_object()) {
+        applicators.dependent_required = dependencies
+            .iter()
+            .map(|(property, required)| DependentRequired {
+                property: property.clone(),
+                required: required
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect(),
+            })
+            .collect();
+    }
+
+    if let Some(dependencies) = schema.get("dependentSchemas").and_then(|v| v.as_object()) {
+        applicators.dependent_schemas = dependencies
+            .iter()
+            .map(|(property, value)| DependentSchema {
+                property: property.clone(),
+                schema: build_schema(value, ctx),
+            })
+            .collect();
+    }
+
+    applicators
+}
+
+/// The `oneOf`, `anyOf` and `allOf` of a schema, leaving out `allOf` when
+/// its members are merged
+fn build_compounds(schema: &Value, merge_all_of: bool, ctx: &mut Context) -> Vec<Compound> {
+    [CompoundKind::One, CompoundKind::Any, CompoundKind::All]
+        .into_iter()
+        .filter(|kind| !(*kind == CompoundKind::All && merge_all_of))
+        .filter_map(|kind| build_compound(schema, kind, ctx))
+        .collect()
+}
+
+fn build_compound(schema: &Value, kind: CompoundKind, ctx: &mut Context) -> Option<Compound> {
+    let branches = schema.get(kind.keyword())?.as_array()?;
+
+    let resolved: Vec<Cow<Value>> = branches
+        .iter()
+        .map(|branch| ctx.resolver.dereference(ctx.scope(), branch))
+        .collect();
+    let openapi = schema.get("discriminator");
+    let discriminator = openapi
+        .and_then(|d| get_string(d, "propertyName"))
+        .or_else(|| find_discriminator(&resolved));
+    let mapping = openapi
+        .and_then(|d| d.get("mapping"))
+        .and_then(|v| v.as_object());
+
+    let options = branches
+        .iter()
+        .zip(&resolved)
+        .map(|(branch, resolved)| {
+            let schema = build_schema(branch, ctx);
+            let reference = schema.reference.as_ref();
+            let value = discriminator
+                .as_deref()
+                .and_then(|property| get_discriminator_value(resolved, property))
+                .or_else(|| {
+                    // OpenAPI maps values to references, by default the
+                    // schema name
+                    let reference = reference.filter(|_| openapi.is_some())?;
+                    let mapped = mapping.into_iter().flatten().find_map(|(value, target)| {
+                        (target.as_str() == Some(&reference.text)).then(|| value.clone())
+                    });
+                    mapped.or_else(|| {
+                        let (_, pointer) = reference.text.split_once('#')?;
+                        resolver::definition_name(pointer)
+                    })
+                });
+            let label = value
+                .or_else(|| schema.title.clone())
+                .or_else(|| reference.and_then(|r| r.definition.clone()));
+            CompoundOption { label, schema }
+        })
+        .collect();
+
+    Some(Compound {
+        kind,
+        discriminator,
+        options,
+    })
+}
+
+/// The first property every option fixes to one value, if there are
+/// several options
+fn find_discriminator(options: &[Cow<Value>]) -> Option<String> {
+    if options.len() < 2 {
+        return None;
+    }
+    let candidates = options[0].get("properties")?.as_object()?;
+
+    candidates
+        .keys()
+        .find(|property| {
+            options
+                .iter()
+                .all(|option| get_discriminator_value(option, property).is_some())
+        })
+        .cloned()
+}
 
-    Ok(html)
+/// Value of `property` in an option: its `const`, or the only value of its
+/// `enum`
+fn get_discriminator_value(option: &Value, property: &str) -> Option<String> {
+    let schema = option.get("properties")?.get(property)?;
+    let value = match (schema.get("const"), schema.get("enum")) {
+        (Some(value), _) => value,
+        (None, Some(Value::Array(values))) if values.len() == 1 => &values[0],
+        _ => return None,
+    };
+
+    Some(match value {
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    })
 }
 
-fn generate_schema_details(schema: &Value, depth: usize) -> Result<String, crate::error::Error> {
-    let mut html = String::new();
+/// Fold the properties, required names and constraints of the `allOf`
+/// members into `node`, noting on each property which member it came from
+fn merge_all_of(schema: &Value, node: &mut Schema, ctx: &mut Context) {
+    let mut required = HashSet::new();
+    collect_all_of(schema, node, &mut required, ctx);
 
-    html.push_str(&format!("<div class=\"schema-details depth-{}\">", depth));
+    // Members often require properties another member defines
+    for property in &mut node.properties {
+        property.required |= required.contains(&property.name);
+    }
+}
 
-    // Constraints
-    let constraints = get_constraints(schema);
-    if !constraints.is_empty() {
-        html.push_str("<div class=\"constraints\">");
-        for constraint in constraints {
-            html.push_str(&format!(
-                "<span class=\"constraint\">{}</span>",
-                escape_html(&constraint)
-            ));
+fn collect_all_of(
+    schema: &Value,
+    node: &mut Schema,
+    required: &mut HashSet<String>,
+    ctx: &mut Context,
+) {
+    required.extend(get_required_fields(schema).into_iter().map(str::to_string));
+    let Some(members) = schema.get("allOf").and_then(|v| v.as_array()) else {
+        return;
+    };
+    // The type of an untyped `allOf` comes from its members
+    let mut untyped = schema.get("type").is_none();
+
+    for (i, member) in members.iter().enumerate() {
+        let entered = ctx.enter(member);
+        if entered.reference.as_ref().is_some_and(|r| !r.expanded) {
+            debug!(
+                "Not merging allOf member {}, its reference isn't expanded",
+                i
+            );
+            ctx.leave();
+            continue;
         }
-        html.push_str("</div>");
-    }
-
-    // Enum values
-    if let Some(enum_values) = schema.get("enum").and_then(|v| v.as_array()) {
-        html.push_str("<div class=\"enum-values\">");
-        html.push_str("<span class=\"enum-label\">Possible values:</span>");
-        for value in enum_values {
-            let val_str = format_json_value(value);
-            html.push_str(&format!(
-                " <span class=\"enum-value\">{}</span>",
-                escape_html(&val_str)
-            ));
+        let resolved = entered.schema.as_ref();
+        let origin = get_string(resolved, "title")
+            .or_else(|| {
+                let reference = entered.reference.as_ref()?;
+                Some(
+                    reference
+                        .definition
+                        .clone()
+                        .unwrap_or(reference.text.clone()),
+                )
+            })
+            .unwrap_or_else(|| format!("allOf/{}", i));
+
+        if untyped && resolved.get("type").is_some() {
+            node.schema_type = get_schema_type(resolved);
+            untyped = false;
         }
-        html.push_str("</div>");
-    }
-
-    // Default value
-    if let Some(default) = schema.get("default") {
-        let default_str = format_json_value(default);
-        html.push_str(&format!(
-            "<div class=\"default-value\">Default: <code>{}</code></div>",
-            escape_html(&default_str)
-        ));
-    }
-
-    // Examples
-    if let Some(examples) = schema.get("examples").and_then(|v| v.as_array()) {
-        if !examples.is_empty() {
-            html.push_str("<div class=\"examples\">");
-            html.push_str("<span class=\"examples-label\">Examples:</span>");
-            for example in examples {
-                let ex_str = match example {
-                    Value::String(s) => s.clone(),
-                    v => v.to_string(),
-                };
-                html.push_str(&format!(" <code>{}</code>", escape_html(&ex_str)));
+
+        let mut member_node = Schema {
+            constraints: get_constraints(resolved),
+            ..Schema::default()
+        };
+        if allows_type(resolved, "object", OBJECT_KEYWORDS) {
+            if let Some(properties) = resolved.get("properties").and_then(|v| v.as_object()) {
+                member_node.properties = build_properties(resolved, properties, ctx);
             }
-            html.push_str("</div>");
         }
-    }
-
-    // Nested properties for objects
-    if schema.get("type").and_then(|v| v.as_str()) == Some("object") {
-        if let Some(properties) = schema.get("properties").and_then(|v| v.as_object()) {
-            let required = get_required_fields(schema);
-
-            html.push_str("<div class=\"nested-properties\">");
-            for (prop_name, prop_schema) in properties {
-                let is_required = required.contains(prop_name.as_str());
-                html.push_str(&generate_property_html(
-                    prop_name,
-                    prop_schema,
-                    is_required,
-                    depth,
-                )?);
+        collect_all_of(resolved, &mut member_node, required, ctx);
+        ctx.leave();
+
+        for mut property in member_node.properties {
+            property.origin.get_or_insert_with(|| origin.clone());
+            match node.properties.iter_mut().find(|p| p.name == property.name) {
+                // Both definitions apply, the first one is documented
+                Some(existing) => {
+                    existing.required |= property.required;
+                    merge_constraints(
+                        &mut existing.schema.constraints,
+                        property.schema.constraints,
+                    );
+                }
+                None => node.properties.push(property),
             }
-            html.push_str("</div>");
         }
+        merge_constraints(&mut node.constraints, member_node.constraints);
     }
+}
 
-    // Array items
-    if schema.get("type").and_then(|v| v.as_str()) == Some("array") {
-        if let Some(items) = schema.get("items") {
-            html.push_str("<div class=\"array-items\">");
-            html.push_str("<div class=\"array-label\">Items:</div>");
-            html.push_str(&generate_schema_details(items, depth)?);
-            html.push_str("</div>");
+fn merge_constraints(constraints: &mut Vec<Constraint>, merged: Vec<Constraint>) {
+    for constraint in merged {
+        if !constraints.contains(&constraint) {
+            constraints.push(constraint);
         }
     }
+}
 
-    html.push_str("</div>");
-
-    Ok(html)
+/// `then` and `else` without an `if` have no effect and are left out
+fn build_conditional(schema: &Value, ctx: &mut Context) -> Option<Conditional> {
+    let condition = Box::new(build_schema(schema.get("if")?, ctx));
+
+    let mut branch = |keyword: &str| {
+        schema
+            .get(keyword)
+            .map(|value| Box::new(build_schema(value, ctx)))
+    };
+    Some(Conditional {
+        condition,
+        then_branch: branch("then"),
+        else_branch: branch("else"),
+    })
 }
 
-fn generate_compound_schema(label: &str, schemas: &[Value]) -> Result<String, crate::error::Error> {
-    let mut html = String::new();
+fn get_string(schema: &Value, keyword: &str) -> Option<String> {
+    schema
+        .get(keyword)
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
 
-    html.push_str(&format!(
-        "<div class=\"compound-schema\"><h3>{}</h3>",
-        label
-    ));
-    html.push_str("<div class=\"compound-options\">");
+fn get_flag(schema: &Value, keyword: &str) -> bool {
+    schema.get(keyword).and_then(|v| v.as_bool()) == Some(true)
+}
 
-    for (i, schema) in schemas.iter().enumerate() {
-        html.push_str(&format!(
-            "<div class=\"compound-option\"><h4>Option {}</h4>",
-            i + 1
-        ));
-        html.push_str(&generate_schema_details(schema, 0)?);
-        html.push_str("</div>");
+/// Whether values of type `name` are documented: `type` is, or lists, `name`,
+/// or is omitted and one of `keywords` is present
+///
+/// Schemas often leave out the type, e.g. `then` branches listing extra
+/// properties.
+fn allows_type(schema: &Value, name: &str, keywords: &[&str]) -> bool {
+    match schema.get("type") {
+        Some(Value::String(schema_type)) => schema_type == name,
+        Some(Value::Array(types)) => types.iter().any(|t| t.as_str() == Some(name)),
+        _ => keywords.iter().any(|keyword| schema.get(keyword).is_some()),
     }
-
-    html.push_str("</div></div>");
-
-    Ok(html)
 }
 
 fn get_schema_type(schema: &Value) -> Option<String> {
     if let Some(type_val) = schema.get("type") {
-        if let Some(type_str) = type_val.as_str() {
-            return Some(type_str.to_string());
+        let mut types: Vec<&str> = match type_val {
+            Value::String(type_str) => vec![type_str],
+            Value::Array(type_arr) => type_arr.iter().filter_map(|v| v.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        // OpenAPI 3.0 has no `null` type, `nullable` allows null instead
+        if !types.is_empty() && get_flag(schema, "nullable") && !types.contains(&"null") {
+            types.push("null");
         }
-        if let Some(type_arr) = type_val.as_array() {
-            let types: Vec<String> = type_arr
-                .iter()
-                .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                .collect();
-            if !types.is_empty() {
-                return Some(types.join(" | "));
-            }
+        if !types.is_empty() {
+            return Some(types.join(" | "));
         }
     }
 
@@ -299,47 +826,67 @@ fn get_required_fields(schema: &Value) -> HashSet<&str> {
         .unwrap_or_default()
 }
 
-fn get_constraints(schema: &Value) -> Vec<String> {
+fn get_constraints(schema: &Value) -> Vec<Constraint> {
     let mut constraints = Vec::new();
 
-    if let Some(min) = schema.get("minimum").and_then(|v| v.as_f64()) {
-        constraints.push(format!("min: {}", min));
+    for (label, bound, exclusive) in [
+        ("min", "minimum", "exclusiveMinimum"),
+        ("max", "maximum", "exclusiveMaximum"),
+    ] {
+        let comparison = if label == "min" { ">" } else { "<" };
+        let exclusive = schema.get(exclusive);
+        if let Some(value) = schema.get(bound).and_then(|v| v.as_f64()) {
+            // A boolean can only be draft-04 (or OpenAPI 3.0) making the
+            // bound itself exclusive, whatever `$schema` says
+            if exclusive.and_then(|v| v.as_bool()) == Some(true) {
+                constraints.push(Constraint::new(label, format!("{} {}", comparison, value)));
+            } else {
+                constraints.push(Constraint::new(label, value));
+            }
+        }
+        if let Some(value) = exclusive.and_then(|v| v.as_f64()) {
+            constraints.push(Constraint::new(label, format!("{} {}", comparison, value)));
+        }
     }
-    if let Some(max) = schema.get("maximum").and_then(|v| v.as_f64()) {
-        constraints.push(format!("max: {}", max));
+    if let Some(multiple_of) = schema.get("multipleOf").and_then(|v| v.as_f64()) {
+        constraints.push(Constraint::new("multipleOf", multiple_of));
     }
     if let Some(min_len) = schema.get("minLength").and_then(|v| v.as_u64()) {
-        constraints.push(format!("minLength: {}", min_len));
+        constraints.push(Constraint::new("minLength", min_len));
     }
     if let Some(max_len) = schema.get("maxLength").and_then(|v| v.as_u64()) {
-        constraints.push(format!("maxLength: {}", max_len));
+        constraints.push(Constraint::new("maxLength", max_len));
     }
     if let Some(pattern) = schema.get("pattern").and_then(|v| v.as_str()) {
-        constraints.push(format!("pattern: {}", pattern));
+        constraints.push(Constraint::new("pattern", pattern));
     }
     if let Some(format) = schema.get("format").and_then(|v| v.as_str()) {
-        constraints.push(format!("format: {}", format));
+        constraints.push(Constraint::new("format", format));
     }
     if let Some(min_items) = schema.get("minItems").and_then(|v| v.as_u64()) {
-        constraints.push(format!("minItems: {}", min_items));
+        constraints.push(Constraint::new("minItems", min_items));
     }
     if let Some(max_items) = schema.get("maxItems").and_then(|v| v.as_u64()) {
-        constraints.push(format!("maxItems: {}", max_items));
+        constraints.push(Constraint::new("maxItems", max_items));
+    }
+    if get_flag(schema, "uniqueItems") {
+        constraints.push(Constraint::flag("uniqueItems"));
     }
-    if schema.get("uniqueItems").and_then(|v| v.as_bool()) == Some(true) {
-        constraints.push("uniqueItems".to_string());
+    if let Some(min_props) = schema.get("minProperties").and_then(|v| v.as_u64()) {
+        constraints.push(Constraint::new("minProperties", min_props));
     }
-    if schema.get("exclusiveMinimum").is_some() {
-        constraints.push("exclusiveMinimum".to_string());
+    if let Some(max_props) = schema.get("maxProperties").and_then(|v| v.as_u64()) {
+        constraints.push(Constraint::new("maxProperties", max_props));
     }
-    if schema.get("exclusiveMaximum").is_some() {
-        constraints.push("exclusiveMaximum".to_string());
+    if let Some(value) = schema.get("const") {
+        constraints.push(Constraint::new("const", format_json_value(value)));
     }
 
     constraints
 }
 
-fn format_json_value(value: &Value) -> String {
+/// Compact JSON for a value, strings keep their quotes
+pub fn format_json_value(value: &Value) -> String {
     match value {
         Value::String(s) => format!("\"{}\"", s),
         Value::Null => "null".to_string(),
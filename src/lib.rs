@@ -3,11 +3,27 @@
 //! This library provides the core functionality for the Deckard CLI tool.
 
 pub mod args;
+pub mod batch;
+pub mod check;
 pub mod commands;
+pub mod config;
 pub mod constants;
+pub mod document;
 pub mod error;
+pub mod example;
 pub mod html;
+pub mod input;
 pub mod json_schema;
+pub mod markdown;
+pub mod output;
+pub mod render;
+pub mod resolver;
+pub mod serve;
+pub mod site;
+pub mod validation;
+pub mod watch;
 
 // Re-export commonly used types
+pub use document::Document;
 pub use error::{Error, Result};
+pub use render::Renderer;
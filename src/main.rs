@@ -1,15 +1,32 @@
-use clap::Parser;
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches, Parser};
+use std::path::Path;
 use tracing::{debug, info};
 
 mod args;
+mod batch;
+mod check;
 mod commands;
+mod config;
 mod constants;
+mod document;
 mod error;
+mod example;
 mod html;
+mod input;
 mod json_schema;
+mod markdown;
+mod output;
+mod render;
+mod resolver;
+mod serve;
+mod site;
+mod validation;
+mod watch;
 
 use args::{effective_log_level, GlobalArgs};
 use commands::Commands;
+use config::Config;
 use error::Result;
 
 #[derive(Parser, Debug)]
@@ -33,18 +50,35 @@ fn main() {
 }
 
 fn run() -> Result<()> {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
 
     // Initialize tracing based on effective log level
     let log_level = effective_log_level(&cli.global);
     init_tracing(log_level);
 
     debug!("CLI arguments: {:?}", cli);
+
+    // Only loaded by the commands it has options for, so a broken config
+    // file doesn't get in the way of the others. The default config file is
+    // optional, an explicitly given one is not
+    let load_config = || -> Result<Config> {
+        let config_explicit = matches.value_source("config") == Some(ValueSource::CommandLine);
+        let config = Config::load(Path::new(&cli.global.config), config_explicit)?;
+        debug!("Configuration: {:?}", config);
+        Ok(config)
+    };
+
     info!("Starting command execution.");
 
     match cli.command {
         Commands::Upgrade(args) => commands::upgrade::execute(args),
-        Commands::Convert(args) => commands::convert::execute(args),
+        Commands::Convert(args) => commands::convert::execute(args, &load_config()?.convert),
+        Commands::Validate(args) => commands::validate::execute(args),
+        Commands::Example(args) => commands::example::execute(args),
+        Commands::Check(args) => commands::check::execute(args),
+        Commands::Serve(args) => commands::serve::execute(args, &load_config()?.convert),
+        Commands::Site(args) => commands::site::execute(args, &load_config()?.convert),
     }
 }
 
@@ -0,0 +1,538 @@
+//! GitHub-flavored Markdown output.
+//!
+//! Properties are flattened into tables using dotted paths (`items[]` for
+//! array items) and definitions get `def-<name>` anchors matching the HTML
+//! output.
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use serde_json::Value;
+
+use crate::document::{
+    Applicators, Compound, Conditional, Document, Property, Reference, Schema, Section,
+};
+use crate::error::Result;
+use crate::json_schema;
+use crate::render::Renderer;
+
+/// Characters escaped in anchor links
+const FRAGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'(')
+    .add(b')')
+    .add(b'<')
+    .add(b'>')
+    .add(b'`');
+
+const TABLE_HEADER: &str =
+    "| Property | Type | Required | Description | Details |\n| --- | --- | --- | --- | --- |\n";
+
+/// Renders GitHub-flavored Markdown
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownRenderer {
+    /// Render the schema and property titles
+    pub titles: bool,
+}
+
+impl Default for MarkdownRenderer {
+    fn default() -> Self {
+        Self { titles: true }
+    }
+}
+
+impl Renderer for MarkdownRenderer {
+    fn render(&self, document: &Document) -> Result<String> {
+        let mut md = String::new();
+
+        if let Some(title) = document.title.as_ref().filter(|_| self.titles) {
+            md.push_str(&format!("# {}\n\n", escape_text(title)));
+        }
+
+        if let Some(description) = &document.description {
+            md.push_str(&escape_text(description));
+            md.push_str("\n\n");
+        }
+
+        for section in &document.sections {
+            match section {
+                Section::Properties(properties) => {
+                    md.push_str("## Properties\n\n");
+                    md.push_str(&self.render_properties_table(properties));
+                    md.push('\n');
+                }
+                Section::ArrayItems(items) => {
+                    md.push_str("## Array Items\n\n");
+                    md.push_str(&self.render_schema_details(items));
+                }
+                Section::Applicators(applicators) => {
+                    md.push_str(&self.render_applicators(applicators));
+                }
+                Section::Conditional(conditional) => {
+                    md.push_str("## Conditional\n\n");
+                    for (label, branch) in branches(conditional) {
+                        md.push_str(&format!("### {}\n\n", label));
+                        md.push_str(&self.render_schema_details(branch));
+                    }
+                }
+                Section::Compound(compound) => md.push_str(&self.render_compound(compound, 3)),
+                Section::Definitions(definitions) => {
+                    md.push_str("## Definitions\n\n");
+                    for definition in definitions {
+                        md.push_str(&format!(
+                            "<a id=\"def-{}\"></a>\n\n### {}\n\n",
+                            json_schema::escape_html(&definition.name),
+                            escape_text(&definition.name)
+                        ));
+                        md.push_str(&self.render_schema_details(&definition.schema));
+                    }
+                }
+                Section::Example(example) => {
+                    md.push_str("## Example\n\n");
+                    md.push_str(&format!(
+                        "```json\n{}\n```\n\n",
+                        serde_json::to_string_pretty(example)?
+                    ));
+                }
+            }
+        }
+
+        Ok(format!("{}\n", md.trim_end()))
+    }
+}
+
+impl MarkdownRenderer {
+    /// Reference, constraints and nested properties of a schema outside a table
+    fn render_schema_details(&self, schema: &Schema) -> String {
+        let mut md = String::new();
+
+        if let Some(reference) = &schema.reference {
+            md.push_str(&render_ref_link(reference));
+            md.push_str("\n\n");
+            if !reference.expanded {
+                return md;
+            }
+        }
+
+        if let Some(boolean) = schema.boolean {
+            md.push_str(&format!("{}\n\n", describe_boolean(boolean)));
+            return md;
+        }
+
+        if let Some(prop_type) = &schema.schema_type {
+            md.push_str(&format!("Type: {}\n\n", code(prop_type)));
+        }
+
+        let details = get_details(schema);
+        if !details.is_empty() {
+            for detail in details {
+                md.push_str(&format!("- {}\n", detail));
+            }
+            md.push('\n');
+        }
+
+        if !schema.properties.is_empty() {
+            md.push_str(&self.render_properties_table(&schema.properties));
+            md.push('\n');
+        }
+
+        if let Some(items) = &schema.items {
+            md.push_str(TABLE_HEADER);
+            self.render_property_rows("[]", items, false, None, &mut md);
+            md.push('\n');
+        }
+
+        md.push_str(&self.render_applicators(&schema.applicators));
+
+        if let Some(conditional) = &schema.conditional {
+            md.push_str(TABLE_HEADER);
+            self.render_conditional_rows("", conditional, &mut md);
+            md.push('\n');
+        }
+
+        for compound in &schema.compounds {
+            md.push_str(&self.render_compound(compound, 4));
+        }
+
+        md
+    }
+
+    /// Dependencies as a list, subschemas as a table with paths relative to
+    /// the schema
+    fn render_applicators(&self, applicators: &Applicators) -> String {
+        let mut md = String::new();
+
+        let dependencies = get_dependencies(applicators);
+        if !dependencies.is_empty() {
+            for dependency in dependencies {
+                md.push_str(&format!("- {}\n", dependency));
+            }
+            md.push('\n');
+        }
+
+        let mut rows = String::new();
+        self.render_applicator_rows("", applicators, &mut rows);
+        if !rows.is_empty() {
+            md.push_str(TABLE_HEADER);
+            md.push_str(&rows);
+            md.push('\n');
+        }
+
+        md
+    }
+
+    fn render_properties_table(&self, properties: &[Property]) -> String {
+        let mut md = String::from(TABLE_HEADER);
+        for property in properties {
+            self.render_property_rows(
+                &property.name,
+                &property.schema,
+                property.required,
+                describe_origin(property),
+                &mut md,
+            );
+        }
+        md
+    }
+
+    /// A table row for the property, followed by rows for its nested properties,
+    /// array items and applicators
+    ///
+    /// `label` says which applicator the row documents or which `allOf`
+    /// member the property was merged from, if any.
+    fn render_property_rows(
+        &self,
+        path: &str,
+        schema: &Schema,
+        required: bool,
+        label: Option<String>,
+        md: &mut String,
+    ) {
+        let mut details: Vec<String> = label.into_iter().collect();
+        if let Some(reference) = &schema.reference {
+            details.push(render_ref_link(reference));
+        }
+        if let Some(boolean) = schema.boolean {
+            details.push(describe_boolean(boolean).to_string());
+        }
+        details.extend(get_details(schema));
+        details.extend(get_dependencies(&schema.applicators));
+
+        // Dependent schemas of the root apply to the root itself
+        let mut name = match path {
+            "" => "(root)".to_string(),
+            _ => code(path),
+        };
+        if let Some(title) = schema.title.as_ref().filter(|_| self.titles) {
+            name.push_str(&format!("<br>{}", escape_text(title)));
+        }
+
+        md.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            name,
+            schema.schema_type.as_deref().map(code).unwrap_or_default(),
+            if required { "yes" } else { "no" },
+            schema
+                .description
+                .as_deref()
+                .map(escape_text)
+                .unwrap_or_default(),
+            details.join("<br>")
+        ));
+
+        for property in &schema.properties {
+            let child = join_path(path, &property.name);
+            let label = describe_origin(property);
+            self.render_property_rows(&child, &property.schema, property.required, label, md);
+        }
+        if let Some(items) = &schema.items {
+            self.render_property_rows(&format!("{}[]", path), items, false, None, md);
+        }
+        self.render_applicator_rows(path, &schema.applicators, md);
+        if let Some(conditional) = &schema.conditional {
+            self.render_conditional_rows(path, conditional, md);
+        }
+        for compound in &schema.compounds {
+            self.render_compound_rows(path, compound, md);
+        }
+    }
+
+    /// Rows for the subschemas of applicators, under made-up path segments:
+    /// `[0]` for prefix items, `[contains]`, `/pattern/`, `*` for additional
+    /// and unevaluated properties and `<name>` for property names
+    fn render_applicator_rows(&self, path: &str, applicators: &Applicators, md: &mut String) {
+        let child = |segment: &str| join_path(path, segment);
+
+        for (i, item) in applicators.prefix_items.iter().enumerate() {
+            let label = "Prefix item".to_string();
+            self.render_property_rows(&format!("{}[{}]", path, i), item, false, Some(label), md);
+        }
+
+        if let Some(contains) = &applicators.contains {
+            let mut label = "Contains".to_string();
+            if let Some(min) = contains.min {
+                label.push_str(&format!("<br>minContains: {}", min));
+            }
+            if let Some(max) = contains.max {
+                label.push_str(&format!("<br>maxContains: {}", max));
+            }
+            let path = format!("{}[contains]", path);
+            self.render_property_rows(&path, &contains.schema, false, Some(label), md);
+        }
+
+        for pattern in &applicators.pattern_properties {
+            let path = child(&format!("/{}/", pattern.pattern));
+            let label = "Pattern properties".to_string();
+            self.render_property_rows(&path, &pattern.schema, false, Some(label), md);
+        }
+
+        let subschemas = [
+            (
+                "*",
+                "Additional properties",
+                &applicators.additional_properties,
+            ),
+            (
+                "*",
+                "Unevaluated properties",
+                &applicators.unevaluated_properties,
+            ),
+            ("<name>", "Property names", &applicators.property_names),
+        ];
+        for (segment, label, subschema) in subschemas {
+            if let Some(subschema) = subschema {
+                let label = label.to_string();
+                self.render_property_rows(&child(segment), subschema, false, Some(label), md);
+            }
+        }
+
+        if let Some(not) = &applicators.not {
+            self.render_property_rows(path, not, false, Some("Not".to_string()), md);
+        }
+
+        for dependency in &applicators.dependent_schemas {
+            let label = format!("When {} is present", code(&dependency.property));
+            self.render_property_rows(path, &dependency.schema, false, Some(label), md);
+        }
+    }
+
+    /// Rows for the `if` schema and its branches, which apply to the schema
+    /// itself and so share its path
+    fn render_conditional_rows(&self, path: &str, conditional: &Conditional, md: &mut String) {
+        for (label, branch) in branches(conditional) {
+            self.render_property_rows(path, branch, false, Some(label.to_string()), md);
+        }
+    }
+
+    /// Rows for the options of a compound, which apply to the schema itself
+    /// and so share its path
+    fn render_compound_rows(&self, path: &str, compound: &Compound, md: &mut String) {
+        for (i, option) in compound.options.iter().enumerate() {
+            let mut label = match &option.label {
+                Some(label) => format!("{}: {}", compound.kind.label(), escape_text(label)),
+                None => format!("{}: Option {}", compound.kind.label(), i + 1),
+            };
+            if let Some(discriminator) = &compound.discriminator {
+                label.push_str(&format!("<br>Discriminator: {}", code(discriminator)));
+            }
+            self.render_property_rows(path, &option.schema, false, Some(label), md);
+        }
+    }
+
+    /// Options of a compound under a level `heading` heading
+    fn render_compound(&self, compound: &Compound, heading: usize) -> String {
+        let mut md = format!("{} {}\n\n", "#".repeat(heading), compound.kind.label());
+        if let Some(discriminator) = &compound.discriminator {
+            md.push_str(&format!("Discriminator: {}\n\n", code(discriminator)));
+        }
+
+        for (i, option) in compound.options.iter().enumerate() {
+            let hashes = "#".repeat(heading + 1);
+            match &option.label {
+                Some(label) => md.push_str(&format!("{} {}\n\n", hashes, escape_text(label))),
+                None => md.push_str(&format!("{} Option {}\n\n", hashes, i + 1)),
+            }
+            md.push_str(&self.render_schema_details(&option.schema));
+        }
+
+        md
+    }
+}
+
+fn join_path(path: &str, name: &str) -> String {
+    match path {
+        "" => name.to_string(),
+        _ => format!("{}.{}", path, name),
+    }
+}
+
+/// The `if` schema and whichever branches are present, with their labels
+fn branches(conditional: &Conditional) -> Vec<(&'static str, &Schema)> {
+    [
+        ("If", Some(&conditional.condition)),
+        ("Then", conditional.then_branch.as_ref()),
+        ("Else", conditional.else_branch.as_ref()),
+    ]
+    .into_iter()
+    .filter_map(|(label, branch)| Some((label, branch?.as_ref())))
+    .collect()
+}
+
+fn describe_origin(property: &Property) -> Option<String> {
+    let origin = property.origin.as_ref()?;
+    Some(format!("From {}", code(origin)))
+}
+
+fn describe_boolean(boolean: bool) -> &'static str {
+    if boolean {
+        "allowed"
+    } else {
+        "not allowed"
+    }
+}
+
+/// `deprecated`, `readOnly` and `writeOnly`, in bold
+fn get_annotations(schema: &Schema) -> Vec<String> {
+    [
+        ("Deprecated", schema.deprecated),
+        ("Read-only", schema.read_only),
+        ("Write-only", schema.write_only),
+    ]
+    .into_iter()
+    .filter(|(_, set)| *set)
+    .map(|(label, _)| format!("**{}**", label))
+    .collect()
+}
+
+/// `dependentRequired` entries, each as one line
+fn get_dependencies(applicators: &Applicators) -> Vec<String> {
+    applicators
+        .dependent_required
+        .iter()
+        .map(|dependency| {
+            let required: Vec<String> = dependency.required.iter().map(|r| code(r)).collect();
+            format!(
+                "{} requires {}",
+                code(&dependency.property),
+                required.join(", ")
+            )
+        })
+        .collect()
+}
+
+/// Annotations, constraints, enum values, default and examples, each as one
+/// line
+fn get_details(schema: &Schema) -> Vec<String> {
+    let mut details = get_annotations(schema);
+    details.extend(
+        schema
+            .constraints
+            .iter()
+            .map(|constraint| escape_text(&constraint.to_string())),
+    );
+
+    if let Some(enum_values) = &schema.enum_values {
+        let values: Vec<String> = enum_values
+            .iter()
+            .map(|value| code(&json_schema::format_json_value(value)))
+            .collect();
+        details.push(format!("Possible values: {}", values.join(", ")));
+    }
+
+    if let Some(default) = &schema.default {
+        details.push(format!(
+            "Default: {}",
+            code(&json_schema::format_json_value(default))
+        ));
+    }
+
+    if !schema.examples.is_empty() {
+        let examples: Vec<String> = schema
+            .examples
+            .iter()
+            .map(|example| match example {
+                Value::String(s) => code(s),
+                v => code(&v.to_string()),
+            })
+            .collect();
+        details.push(format!("Examples: {}", examples.join(", ")));
+    }
+
+    details
+}
+
+fn render_ref_link(reference: &Reference) -> String {
+    let label = if reference.recursive {
+        "recursive: see"
+    } else {
+        "See"
+    };
+    let target = match (&reference.href, &reference.definition) {
+        (Some(href), name) => format!(
+            "[{}]({})",
+            escape_text(name.as_deref().unwrap_or(&reference.text)),
+            utf8_percent_encode(href, FRAGMENT)
+        ),
+        (None, Some(name)) => format!(
+            "[{}](#def-{})",
+            escape_text(name),
+            utf8_percent_encode(name, FRAGMENT)
+        ),
+        (None, None) => code(&reference.text),
+    };
+
+    format!("{} {}", label, target)
+}
+
+/// Inline code span, safe to use inside table cells
+fn code(text: &str) -> String {
+    let text = text.replace('|', "\\|").replace('\n', " ");
+    // The fence must be longer than any run of backticks in the text
+    let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest + 1);
+    if longest > 0 {
+        format!("{} {} {}", fence, text, fence)
+    } else {
+        format!("{}{}{}", fence, text, fence)
+    }
+}
+
+/// Escape text so it's shown literally, inside table cells too
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for line in text.trim().lines() {
+        if !escaped.is_empty() {
+            escaped.push_str("<br>");
+        }
+        for c in line.chars() {
+            match c {
+                '\\' | '`' | '*' | '_' | '[' | ']' | '|' | '#' => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '&' => escaped.push_str("&amp;"),
+                _ => escaped.push(c),
+            }
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_span() {
+        assert_eq!(code("string"), "`string`");
+        assert_eq!(code("a | b"), "`a \\| b`");
+        assert_eq!(code("a`b"), "`` a`b ``");
+    }
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("a_b | <c>"), "a\\_b \\| &lt;c&gt;");
+        assert_eq!(escape_text("line one\nline two\n"), "line one<br>line two");
+    }
+}
@@ -0,0 +1,35 @@
+//! Output formats of the generated documentation.
+
+use clap::ValueEnum;
+use serde::Deserialize;
+
+/// Format of the generated documentation
+#[derive(ValueEnum, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// HTML markup
+    #[default]
+    Html,
+    /// GitHub-flavored Markdown
+    Markdown,
+}
+
+impl OutputFormat {
+    /// File extension of generated files, without the dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Html => "html",
+            OutputFormat::Markdown => "md",
+        }
+    }
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            OutputFormat::Html => "HTML",
+            OutputFormat::Markdown => "Markdown",
+        };
+        write!(f, "{}", s)
+    }
+}
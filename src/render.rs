@@ -0,0 +1,63 @@
+//! Output backends.
+
+use crate::document::Document;
+use crate::error::Result;
+
+/// Turns a [`Document`] into an output format
+///
+/// HTML and Markdown are built in, see
+/// [`HtmlRenderer`](crate::html::HtmlRenderer) and
+/// [`MarkdownRenderer`](crate::markdown::MarkdownRenderer). Other crates can
+/// implement this to render [`json_schema::build_document`] output their own
+/// way.
+///
+/// [`json_schema::build_document`]: crate::json_schema::build_document
+pub trait Renderer {
+    fn render(&self, document: &Document) -> Result<String>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::Section;
+    use crate::json_schema::{build_document, Options};
+    use serde_json::json;
+
+    /// Lists property names, like a downstream backend would
+    struct PropertyNames;
+
+    impl Renderer for PropertyNames {
+        fn render(&self, document: &Document) -> Result<String> {
+            let mut names = Vec::new();
+            for section in &document.sections {
+                if let Section::Properties(properties) = section {
+                    names.extend(properties.iter().map(|p| p.name.as_str()));
+                }
+            }
+            Ok(names.join(","))
+        }
+    }
+
+    #[test]
+    fn test_custom_renderer() {
+        let schema = json!({
+            "type": "object",
+            "properties": {"id": {"$ref": "#/$defs/Id"}, "name": {"type": "string"}},
+            "$defs": {"Id": {"type": "integer", "minimum": 1}}
+        });
+        let document = build_document(&schema, &Options::default()).unwrap();
+
+        assert_eq!(PropertyNames.render(&document).unwrap(), "id,name");
+
+        let Section::Properties(properties) = &document.sections[0] else {
+            panic!("expected properties first");
+        };
+        let id = &properties[0].schema;
+        assert_eq!(id.schema_type.as_deref(), Some("integer"));
+        assert_eq!(id.constraints[0].to_string(), "min: 1");
+        assert_eq!(
+            id.reference.as_ref().and_then(|r| r.definition.as_deref()),
+            Some("Id")
+        );
+    }
+}
@@ -0,0 +1,733 @@
+//! `$ref` resolution for JSON Schema documents.
+//!
+//! References are resolved against the base URI of the schema they appear in,
+//! which starts as the location of the input document and is changed by any
+//! `$id` along the way. Targets that aren't part of an already loaded document
+//! are read from disk relative to the referencing file, and each file is
+//! parsed only once.
+
+use percent_encoding::percent_decode_str;
+use serde_json::Value;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::error::{Error, Result};
+use crate::input;
+
+/// Upper bound on `$ref` -> `$ref` hops before giving up on a chain.
+const MAX_REF_CHAIN: usize = 32;
+
+/// Keywords whose values are instance data rather than subschemas.
+const DATA_KEYWORDS: &[&str] = &["const", "default", "enum", "example", "examples"];
+
+/// Keywords whose values map arbitrary names to subschemas.
+const NAMED_KEYWORDS: &[&str] = &[
+    "$defs",
+    "definitions",
+    "dependentSchemas",
+    "patternProperties",
+    "properties",
+];
+
+/// Where a schema lives and which base URI its references resolve against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scope {
+    /// Base URI, as changed by `$id`.
+    pub base: Url,
+    /// Location the containing document was loaded from.
+    pub location: Url,
+}
+
+impl Scope {
+    /// Scope of a document loaded from `location`.
+    pub fn new(location: Url) -> Self {
+        Self {
+            base: location.clone(),
+            location,
+        }
+    }
+
+    /// Scope of a subschema, applying its `$id` if it has one.
+    pub fn enter(&self, schema: &Value) -> Scope {
+        match schema_id(schema).and_then(|id| self.base.join(id).ok()) {
+            Some(base) => Scope {
+                base,
+                location: self.location.clone(),
+            },
+            None => self.clone(),
+        }
+    }
+}
+
+/// A `$ref` target along with where it was found.
+#[derive(Debug, Clone)]
+pub struct Resolved {
+    pub schema: Value,
+    pub scope: Scope,
+    /// Location of the document containing the target.
+    pub location: Url,
+    /// JSON Pointer to the target within that document.
+    pub pointer: String,
+}
+
+impl Resolved {
+    /// Canonical `location#pointer` identifying the target.
+    pub fn id(&self) -> String {
+        format!("{}#{}", self.location, self.pointer)
+    }
+}
+
+/// Resolves `$ref`s across the input document and the files it references.
+pub struct Resolver {
+    root: Url,
+    /// Parsed documents, keyed by the location they were loaded from.
+    documents: RefCell<HashMap<Url, Rc<Value>>>,
+    /// `$id` and `$anchor` URIs mapped to a document location and pointer.
+    ids: RefCell<HashMap<Url, (Url, String)>>,
+    /// Locations that failed to load, so they're only reported once.
+    missing: RefCell<HashSet<Url>>,
+}
+
+impl Resolver {
+    /// Create a resolver for `schema`, loaded from `location`.
+    pub fn new(schema: &Value, location: Url) -> Self {
+        let resolver = Self {
+            root: location.clone(),
+            documents: RefCell::new(HashMap::new()),
+            ids: RefCell::new(HashMap::new()),
+            missing: RefCell::new(HashSet::new()),
+        };
+        resolver.register(location, schema.clone());
+        resolver
+    }
+
+    /// Scope of the input document.
+    pub fn root_scope(&self) -> Scope {
+        let documents = self.documents.borrow();
+        Scope::new(self.root.clone()).enter(&documents[&self.root])
+    }
+
+    /// Look up the schema a `$ref` points at, following chained references.
+    pub fn resolve(&self, scope: &Scope, reference: &str) -> Option<Resolved> {
+        let mut resolved = self.lookup(scope, reference)?;
+
+        for _ in 0..MAX_REF_CHAIN {
+            let next = match resolved.schema.get("$ref").and_then(|v| v.as_str()) {
+                Some(next) => next.to_string(),
+                None => return Some(resolved),
+            };
+            resolved = self.lookup(&resolved.scope, &next)?;
+        }
+
+        None
+    }
+
+    /// Replace a `$ref` schema with its target, keeping any sibling keywords.
+    ///
+    /// `scope` is the scope of `schema` itself. Schemas without a `$ref`, or
+    /// whose reference can't be resolved, are returned unchanged.
+    pub fn dereference<'v>(&self, scope: &Scope, schema: &'v Value) -> Cow<'v, Value> {
+        let Some(reference) = schema.get("$ref").and_then(|v| v.as_str()) else {
+            return Cow::Borrowed(schema);
+        };
+
+        match self.resolve(scope, reference) {
+            Some(target) => Cow::Owned(merge(schema, target.schema)),
+            None => Cow::Borrowed(schema),
+        }
+    }
+
+    /// Name of the root document definition a target is, if any.
+    pub fn definition_name(&self, resolved: &Resolved) -> Option<String> {
+        if resolved.location != self.root {
+            return None;
+        }
+        definition_name(&resolved.pointer)
+    }
+
+    /// Load every document the loaded ones reference, directly or through
+    /// other documents.
+    pub fn load_references(&self) {
+        let mut visited = HashSet::new();
+        loop {
+            let pending: Vec<(Url, Rc<Value>)> = self
+                .documents
+                .borrow()
+                .iter()
+                .filter(|(location, _)| !visited.contains(*location))
+                .map(|(location, document)| (location.clone(), document.clone()))
+                .collect();
+            if pending.is_empty() {
+                break;
+            }
+
+            for (location, document) in pending {
+                let mut references = Vec::new();
+                collect_refs(
+                    &document,
+                    &Scope::new(location.clone()),
+                    false,
+                    &mut references,
+                );
+                for (scope, reference) in references {
+                    self.lookup(&scope, &reference);
+                }
+                visited.insert(location);
+            }
+        }
+    }
+
+    /// Paths of the loaded documents that are files, sorted.
+    pub fn files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = self
+            .documents
+            .borrow()
+            .keys()
+            .filter_map(|location| location.to_file_path().ok())
+            .collect();
+        files.sort();
+        files
+    }
+
+    fn lookup(&self, scope: &Scope, reference: &str) -> Option<Resolved> {
+        let target = scope.base.join(reference).ok()?;
+
+        let found = self.find(&target).or_else(|| {
+            // `$id`s rarely match where files live on disk, so fall back to
+            // the location of the referencing document
+            let target = scope.location.join(reference).ok()?;
+            self.find(&target)
+        });
+
+        let (location, pointer) = found?;
+        let document = self.documents.borrow().get(&location).cloned()?;
+        let schema = document.pointer(&pointer)?.clone();
+        let scope = scope_at(&document, &location, &pointer);
+
+        Some(Resolved {
+            schema,
+            scope,
+            location,
+            pointer,
+        })
+    }
+
+    /// Find the document location and pointer an absolute URI refers to.
+    fn find(&self, target: &Url) -> Option<(Url, String)> {
+        let mut resource = target.clone();
+        resource.set_fragment(None);
+        let fragment = percent_decode_str(target.fragment().unwrap_or(""))
+            .decode_utf8()
+            .ok()?;
+
+        // Plain name fragments are `$anchor`s, of the document at `resource`
+        // unless it's an embedded schema's `$id`
+        if !fragment.is_empty() && !fragment.starts_with('/') {
+            if !self.ids.borrow().contains_key(&resource) {
+                self.load(&resource)?;
+            }
+            return self.ids.borrow().get(target).cloned();
+        }
+
+        let known = self.ids.borrow().get(&resource).cloned();
+        let (location, prefix) = match known {
+            Some(found) => found,
+            None => {
+                self.load(&resource)?;
+                (resource, String::new())
+            }
+        };
+
+        Some((location, format!("{}{}", prefix, fragment)))
+    }
+
+    /// Load and register the document at `location` unless already cached.
+    fn load(&self, location: &Url) -> Option<()> {
+        if self.documents.borrow().contains_key(location) {
+            return Some(());
+        }
+        if self.missing.borrow().contains(location) {
+            return None;
+        }
+
+        let path = location.to_file_path().ok()?;
+        match input::read(&path, None) {
+            Ok(document) => {
+                debug!("Loaded referenced schema: {}", path.display());
+                self.register(location.clone(), document);
+                Some(())
+            }
+            Err(e) => {
+                warn!("Unable to load referenced schema: {}", e);
+                self.missing.borrow_mut().insert(location.clone());
+                None
+            }
+        }
+    }
+
+    fn register(&self, location: Url, document: Value) {
+        let mut ids = Vec::new();
+        collect_ids(
+            &document,
+            &Scope::new(location.clone()),
+            "",
+            false,
+            &mut ids,
+        );
+
+        let mut index = self.ids.borrow_mut();
+        for (id, pointer) in ids {
+            index
+                .entry(id)
+                .or_insert_with(|| (location.clone(), pointer));
+        }
+        self.documents
+            .borrow_mut()
+            .insert(location, Rc::new(document));
+    }
+}
+
+/// Overlay the sibling keywords of a `$ref` schema onto its target.
+pub fn merge(schema: &Value, target: Value) -> Value {
+    let mut merged = target;
+    if let (Some(merged), Some(siblings)) = (merged.as_object_mut(), schema.as_object()) {
+        merged.remove("$ref");
+        for (key, value) in siblings {
+            if key != "$ref" && key != "$id" {
+                merged.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    merged
+}
+
+/// Location used to resolve references in a document read from `path`,
+/// or from stdin when there is no path.
+pub fn document_location(path: Option<&Path>) -> Result<Url> {
+    let cwd = std::env::current_dir().map_err(Error::Io)?;
+    let location = match path {
+        Some(path) => Url::from_file_path(cwd.join(path)),
+        None => Url::from_directory_path(&cwd),
+    };
+
+    location.map_err(|_| Error::Other("Unable to determine the schema location.".to_string()))
+}
+
+/// Name of the definition a JSON Pointer targets, if it points at
+/// `/definitions/<name>`, `/$defs/<name>` or an OpenAPI
+/// `/components/schemas/<name>` directly.
+pub fn definition_name(pointer: &str) -> Option<String> {
+    let name = pointer
+        .strip_prefix("/definitions/")
+        .or_else(|| pointer.strip_prefix("/$defs/"))
+        .or_else(|| pointer.strip_prefix("/components/schemas/"))?;
+
+    if name.contains('/') {
+        return None;
+    }
+
+    Some(unescape_token(name))
+}
+
+/// The subschema of `document` at a JSON Pointer, written as `/a/b` or as
+/// a percent-encoded `#/a/b` URI fragment, along with the pointer in the
+/// `/a/b` form.
+pub fn select<'v>(document: &'v Value, pointer: &str) -> Result<(&'v Value, String)> {
+    let normalized = match pointer.strip_prefix('#') {
+        Some(fragment) => percent_decode_str(fragment)
+            .decode_utf8()
+            .map_err(|_| Error::Other(format!("Invalid JSON Pointer '{}'.", pointer)))?
+            .into_owned(),
+        None => pointer.to_string(),
+    };
+    if !normalized.is_empty() && !normalized.starts_with('/') {
+        return Err(Error::Other(format!(
+            "Invalid JSON Pointer '{}', pointers start with '/' like '/$defs/Name'.",
+            pointer
+        )));
+    }
+
+    let schema = document
+        .pointer(&normalized)
+        .ok_or_else(|| Error::Other(format!("No schema at '{}' in the document.", pointer)))?;
+    Ok((schema, normalized))
+}
+
+/// Escape a JSON Pointer reference token.
+pub fn escape_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+fn unescape_token(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+/// `$id` (or draft-04 `id`) of a schema, ignoring fragment-only values.
+fn schema_id(schema: &Value) -> Option<&str> {
+    let id = schema
+        .get("$id")
+        .or_else(|| schema.get("id"))
+        .and_then(|v| v.as_str())?;
+
+    if id.starts_with('#') {
+        None
+    } else {
+        Some(id)
+    }
+}
+
+/// Scope of the subschema at `pointer`, applying every `$id` on the way.
+pub fn scope_at(document: &Value, location: &Url, pointer: &str) -> Scope {
+    let mut scope = Scope::new(location.clone()).enter(document);
+    let mut current = document;
+
+    for token in pointer.split('/').skip(1) {
+        let token = unescape_token(token);
+        let next = match current {
+            Value::Object(map) => map.get(&token),
+            Value::Array(items) => token.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        };
+        match next {
+            Some(next) => {
+                current = next;
+                scope = scope.enter(current);
+            }
+            None => break,
+        }
+    }
+
+    scope
+}
+
+fn collect_ids(
+    schema: &Value,
+    scope: &Scope,
+    pointer: &str,
+    named: bool,
+    ids: &mut Vec<(Url, String)>,
+) {
+    match schema {
+        Value::Object(map) if named => {
+            for (key, value) in map {
+                let pointer = format!("{}/{}", pointer, escape_token(key));
+                collect_ids(value, scope, &pointer, false, ids);
+            }
+        }
+        Value::Object(map) => {
+            let scope = scope.enter(schema);
+            if schema_id(schema).is_some() {
+                ids.push((scope.base.clone(), pointer.to_string()));
+            }
+
+            let anchor = map
+                .get("$anchor")
+                .and_then(|v| v.as_str())
+                .or_else(|| schema_id_anchor(schema));
+            if let Some(anchor) = anchor {
+                let mut id = scope.base.clone();
+                id.set_fragment(Some(anchor));
+                ids.push((id, pointer.to_string()));
+            }
+
+            for (key, value) in map {
+                if DATA_KEYWORDS.contains(&key.as_str()) {
+                    continue;
+                }
+                let pointer = format!("{}/{}", pointer, escape_token(key));
+                let named = NAMED_KEYWORDS.contains(&key.as_str());
+                collect_ids(value, &scope, &pointer, named, ids);
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                collect_ids(item, scope, &format!("{}/{}", pointer, i), false, ids);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Every `$ref` in a document with the scope it resolves in.
+fn collect_refs(schema: &Value, scope: &Scope, named: bool, refs: &mut Vec<(Scope, String)>) {
+    match schema {
+        Value::Object(map) if named => {
+            for value in map.values() {
+                collect_refs(value, scope, false, refs);
+            }
+        }
+        Value::Object(map) => {
+            let scope = scope.enter(schema);
+            if let Some(reference) = map.get("$ref").and_then(|v| v.as_str()) {
+                refs.push((scope.clone(), reference.to_string()));
+            }
+
+            for (key, value) in map {
+                if DATA_KEYWORDS.contains(&key.as_str()) {
+                    continue;
+                }
+                let named = NAMED_KEYWORDS.contains(&key.as_str());
+                collect_refs(value, &scope, named, refs);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_refs(item, scope, false, refs);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Files a schema read from `path` is made of: the file itself and every
+/// file it reaches through `$ref`s.
+pub fn referenced_files(schema: &Value, path: &Path) -> Result<Vec<PathBuf>> {
+    let resolver = Resolver::new(schema, document_location(Some(path))?);
+    resolver.load_references();
+    Ok(resolver.files())
+}
+
+/// Draft-06/07 style `"$id": "#name"` anchors.
+fn schema_id_anchor(schema: &Value) -> Option<&str> {
+    schema
+        .get("$id")
+        .and_then(|v| v.as_str())
+        .and_then(|id| id.strip_prefix('#'))
+        .filter(|anchor| !anchor.is_empty() && !anchor.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn location(dir: &Path, name: &str) -> Url {
+        Url::from_file_path(dir.join(name)).unwrap()
+    }
+
+    #[test]
+    fn test_definition_name() {
+        assert_eq!(definition_name("/$defs/Foo").as_deref(), Some("Foo"));
+        assert_eq!(definition_name("/definitions/Bar").as_deref(), Some("Bar"));
+        assert_eq!(definition_name("/definitions/a~1b").as_deref(), Some("a/b"));
+        assert_eq!(
+            definition_name("/components/schemas/Pet").as_deref(),
+            Some("Pet")
+        );
+        assert_eq!(definition_name("/$defs/Foo/properties/id"), None);
+        assert_eq!(definition_name("/properties/name"), None);
+    }
+
+    #[test]
+    fn test_resolve_local() {
+        let dir = TempDir::new().unwrap();
+        let root = json!({
+            "properties": { "name": { "type": "string" } },
+            "$defs": {
+                "Alias": { "$ref": "#/$defs/Id" },
+                "Id": { "type": "integer" },
+                "Spaced Name": { "type": "boolean" }
+            }
+        });
+        let resolver = Resolver::new(&root, location(dir.path(), "root.json"));
+        let scope = resolver.root_scope();
+
+        let name = resolver.resolve(&scope, "#/properties/name").unwrap();
+        assert_eq!(name.schema, json!({ "type": "string" }));
+        assert_eq!(name.pointer, "/properties/name");
+
+        let alias = resolver.resolve(&scope, "#/$defs/Alias").unwrap();
+        assert_eq!(alias.schema, json!({ "type": "integer" }));
+        assert_eq!(resolver.definition_name(&alias).as_deref(), Some("Id"));
+
+        let spaced = resolver.resolve(&scope, "#/$defs/Spaced%20Name").unwrap();
+        assert_eq!(spaced.schema, json!({ "type": "boolean" }));
+
+        assert!(resolver.resolve(&scope, "#/$defs/Missing").is_none());
+    }
+
+    #[test]
+    fn test_resolve_cycle() {
+        let dir = TempDir::new().unwrap();
+        let root = json!({
+            "$defs": {
+                "A": { "$ref": "#/$defs/B" },
+                "B": { "$ref": "#/$defs/A" }
+            }
+        });
+        let resolver = Resolver::new(&root, location(dir.path(), "root.json"));
+
+        assert!(resolver
+            .resolve(&resolver.root_scope(), "#/$defs/A")
+            .is_none());
+    }
+
+    #[test]
+    fn test_resolve_external_file() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("common")).unwrap();
+        fs::write(
+            dir.path().join("common/device.json"),
+            r##"{"$defs":{"Id":{"$ref":"#/$defs/Serial"},"Serial":{"type":"string"}}}"##,
+        )
+        .unwrap();
+
+        let root = json!({});
+        let resolver = Resolver::new(&root, location(dir.path(), "root.json"));
+        let resolved = resolver
+            .resolve(&resolver.root_scope(), "./common/device.json#/$defs/Id")
+            .unwrap();
+
+        assert_eq!(resolved.schema, json!({ "type": "string" }));
+        assert_eq!(
+            resolved.location,
+            location(dir.path(), "common/device.json")
+        );
+        assert_eq!(resolved.pointer, "/$defs/Serial");
+        assert_eq!(resolver.definition_name(&resolved), None);
+    }
+
+    #[test]
+    fn test_resolve_external_anchor() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("common.json"),
+            r#"{"$defs":{"Id":{"$anchor":"id","type":"string"}}}"#,
+        )
+        .unwrap();
+
+        let root = json!({});
+        let resolver = Resolver::new(&root, location(dir.path(), "root.json"));
+        let resolved = resolver
+            .resolve(&resolver.root_scope(), "common.json#id")
+            .unwrap();
+
+        assert_eq!(resolved.schema["type"], "string");
+        assert_eq!(resolved.location, location(dir.path(), "common.json"));
+        assert_eq!(resolved.pointer, "/$defs/Id");
+    }
+
+    #[test]
+    fn test_resolve_missing_file() {
+        let dir = TempDir::new().unwrap();
+        let root = json!({});
+        let resolver = Resolver::new(&root, location(dir.path(), "root.json"));
+
+        assert!(resolver
+            .resolve(&resolver.root_scope(), "missing.json")
+            .is_none());
+    }
+
+    #[test]
+    fn test_resolve_with_ids() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("address.json"),
+            r#"{"$id":"https://example.com/schemas/address.json","type":"object"}"#,
+        )
+        .unwrap();
+
+        let root = json!({
+            "$id": "https://example.com/schemas/root.json",
+            "$defs": {
+                "Embedded": {
+                    "$id": "embedded.json",
+                    "$defs": { "Inner": { "type": "number" } }
+                },
+                "Named": { "$anchor": "named", "type": "null" }
+            }
+        });
+        let resolver = Resolver::new(&root, location(dir.path(), "root.json"));
+        let scope = resolver.root_scope();
+
+        // Embedded `$id` resources resolve without touching the disk
+        let inner = resolver
+            .resolve(&scope, "embedded.json#/$defs/Inner")
+            .unwrap();
+        assert_eq!(inner.schema, json!({ "type": "number" }));
+        assert_eq!(inner.pointer, "/$defs/Embedded/$defs/Inner");
+
+        let named = resolver.resolve(&scope, "#named").unwrap();
+        assert_eq!(named.pointer, "/$defs/Named");
+
+        // Unknown `$id`-relative references fall back to the file location
+        let address = resolver.resolve(&scope, "address.json").unwrap();
+        assert_eq!(
+            address.scope.base.as_str(),
+            "https://example.com/schemas/address.json"
+        );
+    }
+
+    #[test]
+    fn test_select() {
+        let document =
+            json!({"$defs": {"a/b": {"type": "string"}, "Net Name": {"type": "integer"}}});
+
+        let (schema, pointer) = select(&document, "/$defs/a~1b").unwrap();
+        assert_eq!(schema, &json!({"type": "string"}));
+        assert_eq!(pointer, "/$defs/a~1b");
+
+        let (schema, pointer) = select(&document, "#/$defs/Net%20Name").unwrap();
+        assert_eq!(schema, &json!({"type": "integer"}));
+        assert_eq!(pointer, "/$defs/Net Name");
+
+        assert_eq!(select(&document, "#").unwrap().0, &document);
+        assert!(select(&document, "/$defs/Missing").is_err());
+        assert!(select(&document, "$defs").is_err());
+    }
+
+    #[test]
+    fn test_dereference_keeps_siblings() {
+        let dir = TempDir::new().unwrap();
+        let root = json!({
+            "$defs": { "Id": { "type": "integer", "description": "An id" } }
+        });
+        let resolver = Resolver::new(&root, location(dir.path(), "root.json"));
+        let schema = json!({ "$ref": "#/$defs/Id", "description": "Device id" });
+
+        assert_eq!(
+            resolver
+                .dereference(&resolver.root_scope(), &schema)
+                .into_owned(),
+            json!({ "type": "integer", "description": "Device id" })
+        );
+    }
+
+    #[test]
+    fn test_referenced_files() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("common")).unwrap();
+        fs::write(
+            dir.path().join("common/device.yaml"),
+            "$defs:\n  Id:\n    $ref: id.json\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("common/id.json"), r#"{"type": "string"}"#).unwrap();
+        fs::write(dir.path().join("unused.json"), "{}").unwrap();
+
+        let root = json!({
+            "properties": {
+                "device": { "$ref": "common/device.yaml#/$defs/Id" },
+                "missing": { "$ref": "missing.json" }
+            },
+            "examples": [{ "$ref": "unused.json" }]
+        });
+        let path = dir.path().join("root.json");
+
+        assert_eq!(
+            referenced_files(&root, &path).unwrap(),
+            vec![
+                dir.path().join("common/device.yaml"),
+                dir.path().join("common/id.json"),
+                path,
+            ]
+        );
+    }
+}
@@ -0,0 +1,510 @@
+//! Local preview server.
+//!
+//! Schemas below a directory are rendered as standalone pages when they're
+//! requested, so every request shows the current state of the files. Pages
+//! subscribe to `/__deckard/events` and reload when a file they were
+//! rendered from changes.
+
+use notify::{RecursiveMode, Watcher};
+use percent_encoding::{percent_decode_str, utf8_percent_encode};
+use serde_json::Value;
+use std::net::SocketAddr;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+use crate::batch;
+use crate::error::{Error, Result};
+use crate::html::{self, HtmlRenderer, Stylesheet};
+use crate::input;
+use crate::json_schema::{self, escape_html};
+use crate::render::Renderer;
+use crate::resolver;
+use crate::watch;
+
+/// Path of the Server-Sent Events stream pages reload from
+const EVENTS_PATH: &str = "/__deckard/events";
+
+/// Largest request head that's read
+const MAX_REQUEST_SIZE: usize = 8 * 1024;
+
+/// A response to a page request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Response {
+    fn html(status: u16, body: String) -> Self {
+        Self {
+            status,
+            content_type: "text/html; charset=utf-8",
+            body,
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            reason(self.status),
+            self.content_type,
+            self.body.len(),
+            self.body
+        )
+        .into_bytes()
+    }
+}
+
+/// Renders the schemas below a directory
+#[derive(Debug, Clone)]
+pub struct Preview {
+    /// Directory the schemas are served from
+    pub root: PathBuf,
+    /// Documentation options, the path is set per schema
+    pub options: json_schema::Options,
+    /// Renderer of the page markup, pages are always standalone
+    pub renderer: HtmlRenderer,
+    pub stylesheet: Stylesheet,
+}
+
+impl Preview {
+    /// Respond to a `GET` of `path`, the percent-encoded request path
+    /// without the query
+    pub fn respond(&self, path: &str) -> Response {
+        if path == "/" {
+            return self.index();
+        }
+
+        match self.schema_path(path) {
+            Some(file) if file.is_file() => self.page(path, &file),
+            _ => Response::html(
+                404,
+                self.document(Some("Not Found"), "<h1>Not Found</h1>", None),
+            ),
+        }
+    }
+
+    /// Files a page was rendered from, to reload it when they change
+    pub fn page_files(&self, path: &str) -> Vec<PathBuf> {
+        let Some(file) = self.schema_path(path) else {
+            return Vec::new();
+        };
+        input::read(&file, None)
+            .and_then(|schema| resolver::referenced_files(&schema, &file))
+            .unwrap_or_else(|_| vec![file])
+    }
+
+    /// The file a request path names, `None` for paths outside the root
+    fn schema_path(&self, path: &str) -> Option<PathBuf> {
+        let decoded = percent_decode_str(path).decode_utf8().ok()?;
+        let relative = Path::new(decoded.trim_start_matches('/'));
+        let safe = relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+
+        (safe && !relative.as_os_str().is_empty()).then(|| self.root.join(relative))
+    }
+
+    fn index(&self) -> Response {
+        let files = match batch::find_inputs(&self.root) {
+            Ok(inputs) => inputs.files,
+            Err(e) => return self.error(&e, "/"),
+        };
+
+        let mut list = String::new();
+        for file in &files {
+            let relative = file.strip_prefix(&self.root).unwrap_or(file);
+            let href = html::path_href(relative);
+            let title = input::read(file, None).ok().and_then(|schema| {
+                schema
+                    .get("title")
+                    .and_then(Value::as_str)
+                    .map(String::from)
+            });
+
+            list.push_str(&format!(
+                "<li><a href=\"/{}\">{}</a>",
+                href,
+                escape_html(&relative.display().to_string())
+            ));
+            if let Some(title) = title {
+                list.push_str(&format!(" &ndash; {}", escape_html(&title)));
+            }
+            list.push_str("</li>");
+        }
+
+        let body = format!(
+            "<div class=\"schema-container\"><h1 class=\"schema-title\">Schemas</h1><ul class=\"schema-index\">{}</ul></div>",
+            list
+        );
+        Response::html(200, self.document(Some("Schemas"), &body, Some("/")))
+    }
+
+    fn page(&self, path: &str, file: &Path) -> Response {
+        let rendered = input::read(file, None).and_then(|schema| {
+            let options = json_schema::Options {
+                path: Some(file.to_path_buf()),
+                ..self.options.clone()
+            };
+            let document = json_schema::build_document(&schema, &options)?;
+            let markup = HtmlRenderer {
+                standalone: None,
+                ..self.renderer.clone()
+            }
+            .render(&document)?;
+            Ok((document.title, markup))
+        });
+
+        match rendered {
+            Ok((title, markup)) => {
+                Response::html(200, self.document(title.as_deref(), &markup, Some(path)))
+            }
+            Err(e) => self.error(&e, path),
+        }
+    }
+
+    /// A page showing what went wrong, which reloads once it's fixed
+    fn error(&self, error: &Error, path: &str) -> Response {
+        let body = format!(
+            "<div class=\"schema-container\"><h1 class=\"schema-title\">Error</h1><pre class=\"error\">{}</pre></div>",
+            escape_html(&error.to_string())
+        );
+        Response::html(500, self.document(Some("Error"), &body, Some(path)))
+    }
+
+    /// Wrap markup in a standalone document, reloading on changes to the
+    /// files of the page at `reload`
+    fn document(&self, title: Option<&str>, body: &str, reload: Option<&str>) -> String {
+        let body = match reload {
+            Some(path) => format!(
+                "{}\n<script>new EventSource(\"{}?path={}\").onmessage = function () {{ location.reload(); }};</script>",
+                body,
+                EVENTS_PATH,
+                utf8_percent_encode(path, html::URL_COMPONENT)
+            ),
+            None => body.to_string(),
+        };
+        html::document(title, &body, &self.stylesheet)
+    }
+}
+
+/// Serve the preview on `address` until the process is stopped
+pub async fn serve(preview: Preview, address: SocketAddr) -> Result<()> {
+    let listener = TcpListener::bind(address).await.map_err(Error::Io)?;
+    run(preview, listener).await
+}
+
+/// Serve the preview on a bound `listener`
+async fn run(preview: Preview, listener: TcpListener) -> Result<()> {
+    let (changes, _) = broadcast::channel(16);
+    // Dropping the watcher stops it
+    let _watcher = watch_root(&preview.root, changes.clone())?;
+
+    info!(
+        "Serving '{}' on http://{}, press Ctrl-C to stop.",
+        preview.root.display(),
+        listener.local_addr().map_err(Error::Io)?
+    );
+
+    let preview = Arc::new(preview);
+    loop {
+        // Failing to accept one connection, e.g. when out of file
+        // descriptors, doesn't stop the others
+        let (stream, peer) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                warn!("Unable to accept a connection: {}", e);
+                continue;
+            }
+        };
+        debug!("Connection from: {}", peer);
+        let preview = preview.clone();
+        let changes = changes.subscribe();
+        tokio::spawn(async move {
+            if let Err(e) = handle(stream, preview, changes).await {
+                debug!("Connection failed: {}", e);
+            }
+        });
+    }
+}
+
+/// Send the paths of changed files below `root`, a burst of changes at once
+fn watch_root(
+    root: &Path,
+    changes: broadcast::Sender<Vec<PathBuf>>,
+) -> Result<notify::RecommendedWatcher> {
+    let (sender, events) = mpsc::channel::<notify::Result<notify::Event>>();
+    let mut watcher = notify::recommended_watcher(sender)
+        .map_err(|e| Error::Other(format!("Unable to watch for changes: {}", e)))?;
+    watcher
+        .watch(root, RecursiveMode::Recursive)
+        .map_err(|e| Error::Other(format!("Unable to watch for changes: {}", e)))?;
+
+    thread::spawn(move || {
+        while let Ok(event) = events.recv() {
+            let mut paths = Vec::new();
+            let mut next = Some(event);
+            while let Some(event) = next {
+                match event {
+                    Ok(event) if watch::is_change(&event.kind) => paths.extend(event.paths),
+                    Ok(_) => {}
+                    Err(e) => warn!("Error watching files: {}", e),
+                }
+                next = events.recv_timeout(watch::DEBOUNCE).ok();
+            }
+            if !paths.is_empty() {
+                debug!("Changed: {:?}", paths);
+                // Nobody listening is fine
+                let _ = changes.send(paths);
+            }
+        }
+    });
+
+    Ok(watcher)
+}
+
+async fn handle(
+    mut stream: TcpStream,
+    preview: Arc<Preview>,
+    changes: broadcast::Receiver<Vec<PathBuf>>,
+) -> std::io::Result<()> {
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 || request.len() + read > MAX_REQUEST_SIZE {
+            return Ok(());
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let mut parts = request.lines().next().unwrap_or_default().split(' ');
+    let (method, target) = (
+        parts.next().unwrap_or_default(),
+        parts.next().unwrap_or("/"),
+    );
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    debug!("{} {}", method, target);
+
+    if method != "GET" {
+        let response = Response {
+            status: 405,
+            content_type: "text/plain; charset=utf-8",
+            body: "Method Not Allowed".to_string(),
+        };
+        return stream.write_all(&response.to_bytes()).await;
+    }
+
+    if path == EVENTS_PATH {
+        let page = query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("path="))
+            .and_then(|page| percent_decode_str(page).decode_utf8().ok())
+            .map(|page| page.into_owned());
+        return events(stream, preview, page, changes).await;
+    }
+
+    let path = path.to_string();
+    let response = tokio::task::spawn_blocking(move || preview.respond(&path))
+        .await
+        .map_err(std::io::Error::other)?;
+    stream.write_all(&response.to_bytes()).await
+}
+
+/// Stream a reload event whenever a file of the page at `page` changes,
+/// or any file for the index
+async fn events(
+    mut stream: TcpStream,
+    preview: Arc<Preview>,
+    page: Option<String>,
+    mut changes: broadcast::Receiver<Vec<PathBuf>>,
+) -> std::io::Result<()> {
+    stream
+        .write_all(
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-store\r\nConnection: keep-alive\r\n\r\n",
+        )
+        .await?;
+
+    loop {
+        let changed = match changes.recv().await {
+            Ok(changed) => changed,
+            Err(broadcast::error::RecvError::Lagged(_)) => Vec::new(),
+            Err(broadcast::error::RecvError::Closed) => return Ok(()),
+        };
+
+        let reload = match page.as_deref() {
+            None | Some("/") => true,
+            Some(page) => {
+                let preview = preview.clone();
+                let page = page.to_string();
+                let files = tokio::task::spawn_blocking(move || preview.page_files(&page))
+                    .await
+                    .map_err(std::io::Error::other)?;
+                // Lagging behind means changes were missed, reload to be safe
+                changed.is_empty() || changed.iter().any(|path| files.contains(path))
+            }
+        };
+        if reload {
+            stream.write_all(b"data: reload\n\n").await?;
+        }
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn preview(dir: &TempDir) -> Preview {
+        Preview {
+            root: dir.path().to_path_buf(),
+            options: json_schema::Options::default(),
+            renderer: HtmlRenderer::default(),
+            stylesheet: Stylesheet::Link("/style.css".to_string()),
+        }
+    }
+
+    fn write_schemas(dir: &TempDir) {
+        fs::create_dir_all(dir.path().join("devices")).unwrap();
+        fs::write(
+            dir.path().join("devices/light.json"),
+            r#"{"title": "Light", "properties": {"id": {"$ref": "../common.yaml"}}}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("common.yaml"), "type: string\n").unwrap();
+    }
+
+    #[test]
+    fn test_index() {
+        let dir = TempDir::new().unwrap();
+        write_schemas(&dir);
+
+        let response = preview(&dir).respond("/");
+        assert_eq!(response.status, 200);
+        assert!(response
+            .body
+            .contains(r#"<li><a href="/common.yaml">common.yaml</a></li>"#));
+        assert!(response.body.contains(
+            r#"<li><a href="/devices/light.json">devices/light.json</a> &ndash; Light</li>"#
+        ));
+        assert!(response
+            .body
+            .contains(r#"new EventSource("/__deckard/events?path=%2F")"#));
+    }
+
+    #[test]
+    fn test_page() {
+        let dir = TempDir::new().unwrap();
+        write_schemas(&dir);
+
+        let response = preview(&dir).respond("/devices/light%2Ejson");
+        assert_eq!(response.status, 200);
+        assert!(response.body.starts_with("<!DOCTYPE html>"));
+        assert!(response.body.contains("<title>Light</title>"));
+        assert!(response
+            .body
+            .contains(r#"<link rel="stylesheet" href="/style.css">"#));
+        assert!(response
+            .body
+            .contains(r#"<span class="type-badge">string</span>"#));
+        assert!(response
+            .body
+            .contains(r#"new EventSource("/__deckard/events?path=%2Fdevices%2Flight%252Ejson")"#));
+
+        assert_eq!(
+            preview(&dir).page_files("/devices/light.json"),
+            vec![
+                dir.path().join("common.yaml"),
+                dir.path().join("devices/light.json")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_error_page() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("broken.json"), "{ broken").unwrap();
+
+        let response = preview(&dir).respond("/broken.json");
+        assert_eq!(response.status, 500);
+        assert!(response.body.contains("Failed to parse JSON from"));
+        assert!(response.body.contains("new EventSource("));
+    }
+
+    #[test]
+    fn test_not_found() {
+        let dir = TempDir::new().unwrap();
+        write_schemas(&dir);
+
+        assert_eq!(preview(&dir).respond("/missing.json").status, 404);
+        assert_eq!(preview(&dir).respond("/devices").status, 404);
+        assert_eq!(preview(&dir).respond("/devices/../common.yaml").status, 404);
+        assert_eq!(preview(&dir).respond("/%2E%2E/secret.json").status, 404);
+    }
+
+    #[test]
+    fn test_serve_over_loopback() {
+        let dir = TempDir::new().unwrap();
+        write_schemas(&dir);
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let listener = runtime.block_on(TcpListener::bind("127.0.0.1:0")).unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = preview(&dir);
+        thread::spawn(move || runtime.block_on(run(server, listener)));
+
+        let request = |target: &str| {
+            let mut stream = std::net::TcpStream::connect(address).unwrap();
+            stream
+                .set_read_timeout(Some(Duration::from_secs(10)))
+                .unwrap();
+            write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", target).unwrap();
+            stream
+        };
+
+        let mut page = String::new();
+        request("/devices/light.json")
+            .read_to_string(&mut page)
+            .unwrap();
+        assert!(page.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(page.contains("<title>Light</title>"));
+
+        let mut events = BufReader::new(request("/__deckard/events?path=%2Fdevices%2Flight.json"));
+        let mut line = String::new();
+        events.read_line(&mut line).unwrap();
+        assert_eq!(line, "HTTP/1.1 200 OK\r\n");
+        while line != "\r\n" {
+            line.clear();
+            events.read_line(&mut line).unwrap();
+        }
+
+        // A file the page references changes
+        fs::write(dir.path().join("common.yaml"), "type: integer\n").unwrap();
+        line.clear();
+        events.read_line(&mut line).unwrap();
+        assert_eq!(line, "data: reload\n");
+    }
+}
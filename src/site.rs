@@ -0,0 +1,398 @@
+//! Documentation sites of many schemas.
+//!
+//! Every schema below a directory gets a page of its own, at the same
+//! relative path with an `.html` extension. References between the schemas
+//! link to each other's pages instead of documenting the target in place.
+//! An index page lists the schemas, and a search index has their titles,
+//! ids, descriptions and property names for client-side search.
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::batch;
+use crate::document::Section;
+use crate::error::{Error, Result};
+use crate::html::{self, HtmlRenderer, Stylesheet};
+use crate::input;
+use crate::json_schema::{self, escape_html};
+use crate::render::Renderer;
+
+/// Page listing every schema of the site
+pub const INDEX_PAGE: &str = "index.html";
+
+/// Search index written next to the index page
+pub const SEARCH_INDEX: &str = "search-index.json";
+
+/// How a site is rendered
+#[derive(Debug, Clone)]
+pub struct Site {
+    /// Title of the index page
+    pub title: String,
+    /// Documentation options, the path and pages are set per schema
+    pub options: json_schema::Options,
+    /// Renderer of the page markup, pages are always standalone
+    pub renderer: HtmlRenderer,
+    pub stylesheet: Stylesheet,
+    pub minify: bool,
+    /// Number of pages to render at once
+    pub jobs: usize,
+}
+
+/// A schema in the index and the search index
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The schema `title`, or its file name
+    pub title: String,
+    /// The schema `$id`
+    pub id: Option<String>,
+    pub description: Option<String>,
+    /// Page URL relative to the index
+    pub url: String,
+    /// Names of the root properties
+    pub properties: Vec<String>,
+    /// Names of the root definitions
+    pub definitions: Vec<String>,
+}
+
+/// Outcome of generating a site
+#[derive(Debug)]
+pub struct Summary {
+    /// Schemas found
+    pub schemas: usize,
+    /// Schemas that failed to render, with why
+    pub failures: Vec<(PathBuf, Error)>,
+}
+
+impl Site {
+    /// Render every schema below `dir` into `output_dir`, along with the
+    /// index and search index. Schemas that fail don't stop the others.
+    pub fn generate(&self, dir: &Path, output_dir: &Path) -> Result<Summary> {
+        let root = fs::canonicalize(dir)
+            .map_err(|e| Error::Other(format!("Unable to read '{}': {}", dir.display(), e)))?;
+        // A site generated inside the directory isn't made of schemas,
+        // though its search index looks like one
+        let output_root = fs::canonicalize(output_dir).ok();
+        let files: Vec<PathBuf> = batch::find_inputs(&root)?
+            .files
+            .into_iter()
+            .filter(|file| {
+                !output_root
+                    .as_ref()
+                    .is_some_and(|out| file.starts_with(out))
+            })
+            .collect();
+        let page_paths: Vec<PathBuf> = files
+            .iter()
+            .map(|file| batch::output_path(&root, file, Path::new(""), "html"))
+            .collect();
+        if let Some(file) = files
+            .iter()
+            .zip(&page_paths)
+            .find_map(|(file, page)| (page == Path::new(INDEX_PAGE)).then_some(file))
+        {
+            return Err(Error::Other(format!(
+                "The page of '{}' would replace the index page.",
+                file.display()
+            )));
+        }
+        batch::check_outputs(&files, &page_paths)?;
+        let pages: Vec<(PathBuf, PathBuf)> = files.into_iter().zip(page_paths).collect();
+
+        let results = batch::run_parallel(&pages, self.jobs, |(file, page)| {
+            self.render_page(file, page, &pages, output_dir)
+        });
+
+        let mut entries = Vec::new();
+        let mut failures = Vec::new();
+        for ((file, _), result) in pages.iter().zip(results) {
+            match result {
+                Ok(entry) => entries.push(entry),
+                Err(e) => failures.push((file.clone(), e)),
+            }
+        }
+        entries.sort_by_key(|entry| entry.title.to_lowercase());
+
+        fs::create_dir_all(output_dir).map_err(Error::Io)?;
+        fs::write(output_dir.join(INDEX_PAGE), self.render_index(&entries)).map_err(Error::Io)?;
+        let search_index = serde_json::to_string(&entries)?;
+        fs::write(output_dir.join(SEARCH_INDEX), search_index).map_err(Error::Io)?;
+
+        Ok(Summary {
+            schemas: pages.len(),
+            failures,
+        })
+    }
+
+    /// Render the page of `file` at `page`, linking to the other `pages`
+    fn render_page(
+        &self,
+        file: &Path,
+        page: &Path,
+        pages: &[(PathBuf, PathBuf)],
+        output_dir: &Path,
+    ) -> Result<Entry> {
+        let schema = input::read(file, None)?;
+        let options = json_schema::Options {
+            path: Some(file.to_path_buf()),
+            pages: pages
+                .iter()
+                .map(|(file, target)| (file.clone(), relative_href(page, target)))
+                .collect(),
+            ..self.options.clone()
+        };
+        let document = json_schema::build_document(&schema, &options)?;
+
+        let markup = HtmlRenderer {
+            standalone: None,
+            ..self.renderer.clone()
+        }
+        .render(&document)?;
+        let nav = format!(
+            "<nav class=\"site-nav\"><a href=\"{}\">{}</a></nav>",
+            escape_html(&relative_href(page, Path::new(INDEX_PAGE))),
+            escape_html(&self.title)
+        );
+        let content = html::document(
+            document.title.as_deref(),
+            &format!("{}\n{}", nav, markup),
+            &self.stylesheet,
+        );
+        let content = if self.minify {
+            html::minify(&content)
+        } else {
+            content
+        };
+
+        let path = output_dir.join(page);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(Error::Io)?;
+        }
+        fs::write(&path, content).map_err(Error::Io)?;
+
+        let mut entry = Entry {
+            title: document.title.clone().unwrap_or_else(|| {
+                file.file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            }),
+            id: schema
+                .get("$id")
+                .or_else(|| schema.get("id"))
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            description: document.description.clone(),
+            url: html::path_href(page),
+            properties: Vec::new(),
+            definitions: Vec::new(),
+        };
+        for section in &document.sections {
+            match section {
+                Section::Properties(properties) => {
+                    entry.properties = properties.iter().map(|p| p.name.clone()).collect();
+                }
+                Section::Definitions(definitions) => {
+                    entry.definitions = definitions.iter().map(|d| d.name.clone()).collect();
+                }
+                _ => {}
+            }
+        }
+
+        Ok(entry)
+    }
+
+    fn render_index(&self, entries: &[Entry]) -> String {
+        let mut body = String::new();
+        body.push_str("<div class=\"schema-container\">");
+        body.push_str(&format!(
+            "<div class=\"schema-header\"><h1 class=\"schema-title\">{}</h1></div>",
+            escape_html(&self.title)
+        ));
+        body.push_str("<ul class=\"site-index\">");
+        for entry in entries {
+            body.push_str("<li class=\"site-entry\">");
+            body.push_str(&format!(
+                "<a class=\"site-link\" href=\"{}\">{}</a>",
+                escape_html(&entry.url),
+                escape_html(&entry.title)
+            ));
+            if let Some(id) = &entry.id {
+                body.push_str(&format!(
+                    " <code class=\"schema-id\">{}</code>",
+                    escape_html(id)
+                ));
+            }
+            if let Some(description) = &entry.description {
+                body.push_str(&format!(
+                    "<p class=\"schema-description\">{}</p>",
+                    escape_html(description)
+                ));
+            }
+            body.push_str("</li>");
+        }
+        body.push_str("</ul>");
+        body.push_str("</div>");
+
+        let content = html::document(Some(&self.title), &body, &self.stylesheet);
+        if self.minify {
+            html::minify(&content)
+        } else {
+            content
+        }
+    }
+}
+
+/// URL of the page at `to` from the page at `from`, both relative to the
+/// site root
+fn relative_href(from: &Path, to: &Path) -> String {
+    let from_dir: Vec<Component> = from
+        .parent()
+        .map(|dir| dir.components().collect())
+        .unwrap_or_default();
+    let to: Vec<Component> = to.components().collect();
+    let common = from_dir.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut relative = PathBuf::new();
+    for _ in common..from_dir.len() {
+        relative.push("..");
+    }
+    for component in &to[common..] {
+        relative.push(component);
+    }
+    html::path_href(&relative)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use tempfile::TempDir;
+
+    fn site() -> Site {
+        Site {
+            title: "Devices".to_string(),
+            options: json_schema::Options::default(),
+            renderer: HtmlRenderer::default(),
+            stylesheet: Stylesheet::Link("https://example.com/docs.css".to_string()),
+            minify: false,
+            jobs: 2,
+        }
+    }
+
+    #[test]
+    fn test_relative_href() {
+        let href = |from: &str, to: &str| relative_href(Path::new(from), Path::new(to));
+
+        assert_eq!(href("a.html", "b.html"), "b.html");
+        assert_eq!(href("a.html", "devices/light.html"), "devices/light.html");
+        assert_eq!(href("devices/light.html", "index.html"), "../index.html");
+        assert_eq!(href("devices/light.html", "devices/fan.html"), "fan.html");
+        assert_eq!(href("a/b/c.html", "a/d/e f.html"), "../d/e%20f.html");
+    }
+
+    #[test]
+    fn test_generate() {
+        let dir = TempDir::new().unwrap();
+        let schemas = dir.path().join("schemas");
+        fs::create_dir_all(schemas.join("devices")).unwrap();
+        fs::write(
+            schemas.join("common.json"),
+            r#"{"title": "Common", "$defs": {"Id": {"type": "string", "description": "An id"}}}"#,
+        )
+        .unwrap();
+        fs::write(
+            schemas.join("devices/light.yaml"),
+            "$id: https://example.com/light\ntitle: Light\ndescription: A light\nproperties:\n  id:\n    $ref: ../common.json#/$defs/Id\n",
+        )
+        .unwrap();
+        fs::write(schemas.join("broken.json"), "{ broken").unwrap();
+        let output = dir.path().join("site");
+
+        let summary = site().generate(&schemas, &output).unwrap();
+        assert_eq!(summary.schemas, 3);
+        assert_eq!(summary.failures.len(), 1);
+        assert!(summary.failures[0].0.ends_with("broken.json"));
+
+        let light = fs::read_to_string(output.join("devices/light.html")).unwrap();
+        assert!(
+            light.contains(r#"<nav class="site-nav"><a href="../index.html">Devices</a></nav>"#)
+        );
+        assert!(light.contains(
+            r#"<span class="ref-label">See</span> <a class="ref-link" href="../common.html#def-Id">Id</a>"#
+        ));
+        // Linked rather than documented in place
+        assert!(light.contains(r#"<div class="property-description">An id</div>"#));
+        assert!(!light.contains("constraints"));
+        assert!(output.join("common.html").exists());
+
+        let index = fs::read_to_string(output.join(INDEX_PAGE)).unwrap();
+        let common = index.find(r#"<a class="site-link" href="common.html">Common</a>"#);
+        let light = index.find(
+            r#"<a class="site-link" href="devices/light.html">Light</a> <code class="schema-id">https://example.com/light</code><p class="schema-description">A light</p>"#,
+        );
+        assert!(common.unwrap() < light.unwrap());
+
+        let search: Value =
+            serde_json::from_str(&fs::read_to_string(output.join(SEARCH_INDEX)).unwrap()).unwrap();
+        assert_eq!(
+            search,
+            serde_json::json!([
+                {
+                    "title": "Common",
+                    "id": null,
+                    "description": null,
+                    "url": "common.html",
+                    "properties": [],
+                    "definitions": ["Id"]
+                },
+                {
+                    "title": "Light",
+                    "id": "https://example.com/light",
+                    "description": "A light",
+                    "url": "devices/light.html",
+                    "properties": ["id"],
+                    "definitions": []
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_page_collision() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.json"), "{}").unwrap();
+        fs::write(dir.path().join("a.yaml"), "{}").unwrap();
+
+        let err = site()
+            .generate(dir.path(), &dir.path().join("site"))
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("would both be written to 'a.html'"));
+    }
+
+    #[test]
+    fn test_output_inside_input() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.json"), r#"{"title": "A"}"#).unwrap();
+        let output = dir.path().join("site");
+
+        for _ in 0..2 {
+            let summary = site().generate(dir.path(), &output).unwrap();
+            assert_eq!(summary.schemas, 1);
+            assert!(summary.failures.is_empty());
+        }
+        assert!(!output.join("site").exists());
+    }
+
+    #[test]
+    fn test_index_page_collision() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("index.json"), "{}").unwrap();
+
+        let err = site()
+            .generate(dir.path(), &dir.path().join("site"))
+            .unwrap_err();
+        assert!(err.to_string().contains("would replace the index page"));
+    }
+}
@@ -0,0 +1,485 @@
+//! Checking that a document is a well-formed JSON Schema.
+//!
+//! Besides validating against the draft meta-schema, which accepts any
+//! keyword, this flags keywords no draft knows (usually typos), invalid
+//! `type` names and `$ref`s that don't resolve. OpenAPI 3.x documents are
+//! checked by their `components/schemas`.
+
+use serde_json::{json, Value};
+use std::fmt;
+use std::path::Path;
+use tracing::debug;
+
+use crate::error::Result;
+use crate::json_schema;
+use crate::resolver::{self, Resolver, Scope};
+
+/// Meta-schema used when the schema has no `$schema`
+const DEFAULT_META_SCHEMA: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Meta-schema of OpenAPI 3.0 schemas, a draft-04 dialect
+const DRAFT4_META_SCHEMA: &str = "http://json-schema.org/draft-04/schema";
+
+/// Meta-schemas bundled with the validator, without their trailing `#`
+const META_SCHEMAS: &[&str] = &[
+    "http://json-schema.org/draft-04/schema",
+    "http://json-schema.org/draft-06/schema",
+    "http://json-schema.org/draft-07/schema",
+    "https://json-schema.org/draft/2019-09/schema",
+    "https://json-schema.org/draft/2020-12/schema",
+];
+
+/// Keywords of any draft, including annotations and the content vocabulary,
+/// and those of OpenAPI schemas
+const KNOWN_KEYWORDS: &[&str] = &[
+    "$anchor",
+    "$comment",
+    "$defs",
+    "$dynamicAnchor",
+    "$dynamicRef",
+    "$id",
+    "$recursiveAnchor",
+    "$recursiveRef",
+    "$ref",
+    "$schema",
+    "$vocabulary",
+    "additionalItems",
+    "additionalProperties",
+    "allOf",
+    "anyOf",
+    "const",
+    "contains",
+    "contentEncoding",
+    "contentMediaType",
+    "contentSchema",
+    "default",
+    "definitions",
+    "dependencies",
+    "dependentRequired",
+    "dependentSchemas",
+    "deprecated",
+    "description",
+    "discriminator",
+    "else",
+    "enum",
+    "example",
+    "examples",
+    "exclusiveMaximum",
+    "exclusiveMinimum",
+    "externalDocs",
+    "format",
+    "id",
+    "if",
+    "items",
+    "maxContains",
+    "maxItems",
+    "maxLength",
+    "maxProperties",
+    "maximum",
+    "minContains",
+    "minItems",
+    "minLength",
+    "minProperties",
+    "minimum",
+    "multipleOf",
+    "not",
+    "nullable",
+    "oneOf",
+    "pattern",
+    "patternProperties",
+    "prefixItems",
+    "properties",
+    "propertyNames",
+    "readOnly",
+    "required",
+    "then",
+    "title",
+    "type",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+    "uniqueItems",
+    "writeOnly",
+    "xml",
+];
+
+/// Keywords whose value is a single subschema
+const SCHEMA_KEYWORDS: &[&str] = &[
+    "additionalItems",
+    "additionalProperties",
+    "contains",
+    "contentSchema",
+    "else",
+    "if",
+    "items",
+    "not",
+    "propertyNames",
+    "then",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+];
+
+/// Keywords whose value is an array of subschemas
+const SCHEMA_ARRAY_KEYWORDS: &[&str] = &["allOf", "anyOf", "items", "oneOf", "prefixItems"];
+
+/// Keywords whose value maps names to subschemas
+const SCHEMA_MAP_KEYWORDS: &[&str] = &[
+    "$defs",
+    "definitions",
+    "dependencies",
+    "dependentSchemas",
+    "patternProperties",
+    "properties",
+];
+
+const TYPES: &[&str] = &[
+    "array", "boolean", "integer", "null", "number", "object", "string",
+];
+
+/// Something wrong with a schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    /// JSON Pointer to the offending value
+    pub pointer: String,
+    pub message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}: {}", self.pointer, self.message)
+    }
+}
+
+/// Check a schema document, `path` is where relative `$ref`s resolve from
+/// (defaults to the current directory)
+///
+/// Returns the problems found sorted by location, empty if the schema is
+/// well-formed.
+pub fn validate(schema: &Value, path: Option<&Path>) -> Result<Vec<Problem>> {
+    let location = resolver::document_location(path)?;
+    let resolver = Resolver::new(schema, location.clone());
+
+    let mut problems = Vec::new();
+    match json_schema::openapi_version(schema) {
+        // The rest of an OpenAPI document isn't a schema
+        Some(version) => {
+            let meta_schema = if version.starts_with("3.0") {
+                DRAFT4_META_SCHEMA
+            } else {
+                DEFAULT_META_SCHEMA
+            };
+            let parent = "/components/schemas";
+            let scope = resolver::scope_at(schema, &location, parent);
+            let components = schema.pointer(parent).and_then(|v| v.as_object());
+            for (name, component) in components.into_iter().flatten() {
+                let pointer = format!("{}/{}", parent, resolver::escape_token(name));
+                problems.extend(check(component, &pointer, &scope, meta_schema, &resolver));
+            }
+        }
+        None => {
+            problems = check(
+                schema,
+                "",
+                &resolver.root_scope(),
+                DEFAULT_META_SCHEMA,
+                &resolver,
+            )
+        }
+    }
+    problems.sort_by(|a, b| a.pointer.cmp(&b.pointer));
+
+    Ok(problems)
+}
+
+/// Problems of the schema at `pointer`, `meta_schema` applies unless it
+/// declares its own `$schema`
+fn check(
+    schema: &Value,
+    pointer: &str,
+    scope: &Scope,
+    meta_schema: &str,
+    resolver: &Resolver,
+) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    check_schema(schema, pointer, scope, resolver, &mut problems);
+
+    // Keep the more specific message when the meta-schema agrees
+    let reported: Vec<String> = problems.iter().map(|p| p.pointer.clone()).collect();
+    let meta_problems = check_meta_schema(schema, meta_schema)
+        .into_iter()
+        .map(|problem| Problem {
+            pointer: format!("{}{}", pointer, problem.pointer),
+            ..problem
+        });
+    problems.extend(meta_problems.filter(|problem| {
+        let nested = format!("{}/", problem.pointer);
+        !reported
+            .iter()
+            .any(|pointer| *pointer == problem.pointer || pointer.starts_with(&nested))
+    }));
+
+    problems
+}
+
+fn check_meta_schema(schema: &Value, default: &str) -> Vec<Problem> {
+    let meta_schema = match schema.get("$schema") {
+        None => default,
+        Some(Value::String(uri)) if META_SCHEMAS.contains(&uri.trim_end_matches('#')) => uri,
+        Some(Value::String(uri)) => {
+            return vec![Problem {
+                pointer: "/$schema".to_string(),
+                message: format!("unknown meta-schema '{}'", uri),
+            }];
+        }
+        // Reported by the meta-schema of the default draft
+        Some(_) => default,
+    };
+    debug!("Validating against meta-schema: {}", meta_schema);
+
+    let validator = jsonschema::options()
+        .build(&json!({ "$ref": meta_schema }))
+        .expect("bundled meta-schemas are valid");
+
+    validator
+        .iter_errors(schema)
+        .map(|error| Problem {
+            pointer: error.instance_path.to_string(),
+            message: error.to_string(),
+        })
+        .collect()
+}
+
+fn check_schema(
+    schema: &Value,
+    pointer: &str,
+    scope: &Scope,
+    resolver: &Resolver,
+    problems: &mut Vec<Problem>,
+) {
+    // Boolean schemas and anything else are left to the meta-schema
+    let Value::Object(map) = schema else {
+        return;
+    };
+    let scope = scope.enter(schema);
+
+    for (key, value) in map {
+        let pointer = format!("{}/{}", pointer, resolver::escape_token(key));
+        let keyword = key.as_str();
+
+        if !KNOWN_KEYWORDS.contains(&keyword) && !keyword.starts_with("x-") {
+            let mut message = format!("unknown keyword '{}'", keyword);
+            if let Some(suggestion) = suggest(keyword, KNOWN_KEYWORDS) {
+                message.push_str(&format!(", did you mean '{}'?", suggestion));
+            }
+            problems.push(Problem { pointer, message });
+            continue;
+        }
+
+        match keyword {
+            "type" => check_type(value, &pointer, problems),
+            "$ref" => {
+                if let Some(reference) = value.as_str() {
+                    if resolver.resolve(&scope, reference).is_none() {
+                        problems.push(Problem {
+                            pointer,
+                            message: format!("unresolved reference '{}'", reference),
+                        });
+                    }
+                }
+            }
+            _ if SCHEMA_MAP_KEYWORDS.contains(&keyword) => {
+                for (name, subschema) in value.as_object().into_iter().flatten() {
+                    let pointer = format!("{}/{}", pointer, resolver::escape_token(name));
+                    check_schema(subschema, &pointer, &scope, resolver, problems);
+                }
+            }
+            _ if SCHEMA_ARRAY_KEYWORDS.contains(&keyword) && value.is_array() => {
+                for (i, subschema) in value.as_array().into_iter().flatten().enumerate() {
+                    let pointer = format!("{}/{}", pointer, i);
+                    check_schema(subschema, &pointer, &scope, resolver, problems);
+                }
+            }
+            _ if SCHEMA_KEYWORDS.contains(&keyword) => {
+                check_schema(value, &pointer, &scope, resolver, problems);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn check_type(value: &Value, pointer: &str, problems: &mut Vec<Problem>) {
+    let names: Vec<(String, &Value)> = match value {
+        Value::Array(types) => types
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (format!("{}/{}", pointer, i), t))
+            .collect(),
+        _ => vec![(pointer.to_string(), value)],
+    };
+
+    for (pointer, name) in names {
+        let Some(name) = name.as_str() else {
+            continue;
+        };
+        if !TYPES.contains(&name) {
+            let mut message = format!("invalid type '{}'", name);
+            if let Some(suggestion) = suggest(name, TYPES) {
+                message.push_str(&format!(", did you mean '{}'?", suggestion));
+            }
+            problems.push(Problem { pointer, message });
+        }
+    }
+}
+
+/// The closest candidate within a couple of typos
+fn suggest<'a>(word: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(word, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance, ignoring case
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(schema: Value) -> Vec<String> {
+        validate(&schema, None)
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_schema() {
+        let schema = json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "name": {"type": ["string", "null"], "x-order": 1},
+                "tags": {"type": "array", "items": {"$ref": "#/definitions/Tag"}}
+            },
+            "definitions": {"Tag": {"type": "string", "enum": [{"requird": true}]}}
+        });
+
+        assert!(messages(schema).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_keyword() {
+        let schema = json!({
+            "type": "object",
+            "requird": ["name"],
+            "properties": {"name": {"type": "string", "minLenght": 1}}
+        });
+
+        assert_eq!(
+            messages(schema),
+            vec![
+                "#/properties/name/minLenght: unknown keyword 'minLenght', did you mean 'minLength'?",
+                "#/requird: unknown keyword 'requird', did you mean 'required'?",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_type() {
+        let schema = json!({
+            "properties": {"a": {"type": "strng"}, "b": {"type": ["null", "integr"]}}
+        });
+
+        assert_eq!(
+            messages(schema),
+            vec![
+                "#/properties/a/type: invalid type 'strng', did you mean 'string'?",
+                "#/properties/b/type/1: invalid type 'integr', did you mean 'integer'?",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dangling_ref() {
+        let schema = json!({
+            "items": {"$ref": "#/$defs/Missing"},
+            "$defs": {"Present": {"type": "string"}}
+        });
+
+        assert_eq!(
+            messages(schema),
+            vec!["#/items/$ref: unresolved reference '#/$defs/Missing'"]
+        );
+    }
+
+    #[test]
+    fn test_meta_schema_errors() {
+        let problems = validate(&json!({"minLength": -1, "required": "name"}), None).unwrap();
+
+        let pointers: Vec<&str> = problems.iter().map(|p| p.pointer.as_str()).collect();
+        assert_eq!(pointers, vec!["/minLength", "/required"]);
+    }
+
+    #[test]
+    fn test_unknown_meta_schema() {
+        assert_eq!(
+            messages(json!({"$schema": "https://example.com/schema"})),
+            vec!["#/$schema: unknown meta-schema 'https://example.com/schema'"]
+        );
+    }
+
+    #[test]
+    fn test_openapi_components() {
+        let document = json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Pets", "version": "1.0.0"},
+            "paths": {},
+            "components": {"schemas": {
+                "Pet": {
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string", "nullable": true, "example": "Rex", "xml": {"attribute": true}},
+                        "weight": {"type": "number", "minimum": 0, "exclusiveMinimum": true},
+                        "owner": {"$ref": "#/components/schemas/Owner"}
+                    }
+                },
+                "Owner": {"type": "object", "maxProperties": -1, "requird": ["id"]}
+            }}
+        });
+
+        assert_eq!(
+            messages(document),
+            vec![
+                "#/components/schemas/Owner/maxProperties: -1 is less than the minimum of 0",
+                "#/components/schemas/Owner/requird: unknown keyword 'requird', did you mean 'required'?",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("required", "required"), 0);
+        assert_eq!(edit_distance("requird", "required"), 1);
+        assert_eq!(edit_distance("Type", "type"), 0);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
@@ -0,0 +1,92 @@
+//! Re-running a task when the files it read change.
+
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+use crate::error::{Error, Result};
+
+/// How long the files have to be left alone before running again, editors
+/// often write a file several times when saving
+pub const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Run `task` now and again whenever one of the files it returns changes.
+///
+/// `task` returns the files to watch until its next run, so the list can
+/// change between runs. A burst of changes causes a single run. Only
+/// returns if watching fails.
+pub fn watch<F>(mut task: F) -> Result<()>
+where
+    F: FnMut() -> Vec<PathBuf>,
+{
+    let (sender, events) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).map_err(watch_error)?;
+    // Directories are watched rather than the files themselves, so files
+    // replaced on save are still noticed
+    let mut watched: HashSet<PathBuf> = HashSet::new();
+
+    loop {
+        let files = task();
+        let directories: HashSet<PathBuf> = files
+            .iter()
+            .filter_map(|file| file.parent())
+            .map(Path::to_path_buf)
+            .collect();
+
+        for directory in watched.difference(&directories) {
+            debug!("No longer watching: {}", directory.display());
+            let _ = watcher.unwatch(directory);
+        }
+        for directory in directories.difference(&watched) {
+            debug!("Watching: {}", directory.display());
+            watcher
+                .watch(directory, RecursiveMode::NonRecursive)
+                .map_err(watch_error)?;
+        }
+        watched = directories;
+
+        // Wait for a change to one of the files, then for things to settle
+        loop {
+            match events.recv().map_err(|e| Error::Other(e.to_string()))? {
+                Ok(event) if changes(&event, &files) => {
+                    debug!("Changed: {:?}", event.paths);
+                    break;
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Error watching files: {}", e),
+            }
+        }
+        // Other activity in the directories, like the output being read,
+        // doesn't put the run off
+        let mut settled = Instant::now() + DEBOUNCE;
+        loop {
+            match events.recv_timeout(settled.saturating_duration_since(Instant::now())) {
+                Ok(Ok(event)) if changes(&event, &files) => settled = Instant::now() + DEBOUNCE,
+                Ok(_) => {}
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(e) => return Err(Error::Other(e.to_string())),
+            }
+        }
+    }
+}
+
+/// Whether `event` may have changed one of `files`
+fn changes(event: &notify::Event, files: &[PathBuf]) -> bool {
+    is_change(&event.kind) && event.paths.iter().any(|path| files.contains(path))
+}
+
+/// Whether an event may have changed a file's content, reading the files
+/// again mustn't trigger another run
+pub fn is_change(kind: &EventKind) -> bool {
+    matches!(
+        kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) | EventKind::Any
+    )
+}
+
+fn watch_error(error: notify::Error) -> Error {
+    Error::Other(format!("Unable to watch for changes: {}", error))
+}
@@ -0,0 +1,175 @@
+use predicates::prelude::*;
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+use crate::support::cli;
+
+const CONFIG_SCHEMA: &str = r##"{
+    "type": "object",
+    "required": ["name"],
+    "properties": {
+        "name": {"type": "string"},
+        "port": {"type": "integer", "maximum": 65535}
+    }
+}"##;
+
+/// Write the schema and documents into `dir`, returning their paths
+fn write_files(dir: &TempDir, documents: &[(&str, &str)]) -> (PathBuf, Vec<PathBuf>) {
+    let schema_path = dir.path().join("schema.json");
+    fs::write(&schema_path, CONFIG_SCHEMA).unwrap();
+
+    let paths = documents
+        .iter()
+        .map(|(name, content)| {
+            let path = dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            path
+        })
+        .collect();
+
+    (schema_path, paths)
+}
+
+#[test]
+fn test_check_help() {
+    cli()
+        .arg("check")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Check documents against a JSON Schema",
+        ))
+        .stdout(predicate::str::contains("--schema"));
+}
+
+#[test]
+fn test_check_valid_documents() {
+    let temp_dir = TempDir::new().unwrap();
+    let (schema_path, paths) = write_files(
+        &temp_dir,
+        &[
+            ("a.json", r#"{"name": "a", "port": 80}"#),
+            ("b.yaml", "name: b\n"),
+        ],
+    );
+
+    cli()
+        .arg("check")
+        .arg("--schema")
+        .arg(&schema_path)
+        .args(&paths)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("a.json' is valid."))
+        .stdout(predicate::str::contains("b.yaml' is valid."));
+}
+
+#[test]
+fn test_check_reports_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let (schema_path, paths) = write_files(
+        &temp_dir,
+        &[
+            ("good.json", r#"{"name": "a"}"#),
+            ("bad.yaml", "port: 70000\n"),
+        ],
+    );
+
+    cli()
+        .arg("check")
+        .arg("-s")
+        .arg(&schema_path)
+        .args(&paths)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("good.json' is valid."))
+        .stdout(predicate::str::contains(
+            "bad.yaml' is invalid:\n  #: \"name\" is a required property (required at #/required)\n  #/port: 70000 is greater than the maximum of 65535 (maximum at #/properties/port/maximum)\n",
+        ))
+        .stderr(predicate::str::contains("1 of 2 document(s) don't match"));
+}
+
+#[test]
+fn test_check_json_report() {
+    let temp_dir = TempDir::new().unwrap();
+    let (schema_path, _) = write_files(&temp_dir, &[]);
+
+    let output = cli()
+        .arg("check")
+        .arg("--schema")
+        .arg(&schema_path)
+        .arg("--format")
+        .arg("json")
+        .write_stdin(r#"{"name": 1}"#)
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let report: Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(report["valid"], Value::Bool(false));
+    assert_eq!(report["documents"][0]["name"], "stdin");
+    assert_eq!(
+        report["documents"][0]["errors"][0]["instance_path"],
+        "/name"
+    );
+    assert_eq!(report["documents"][0]["errors"][0]["keyword"], "type");
+    assert_eq!(
+        report["documents"][0]["errors"][0]["schema_path"],
+        "/properties/name/type"
+    );
+}
+
+#[test]
+fn test_check_missing_document() {
+    let temp_dir = TempDir::new().unwrap();
+    let (schema_path, _) = write_files(&temp_dir, &[]);
+
+    cli()
+        .arg("check")
+        .arg("--schema")
+        .arg(&schema_path)
+        .arg(temp_dir.path().join("missing.json"))
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("is invalid:"))
+        .stdout(predicate::str::contains("not found"))
+        .stderr(predicate::str::contains("1 of 1 document(s)"));
+}
+
+#[test]
+fn test_check_reports_unreadable_documents() {
+    let temp_dir = TempDir::new().unwrap();
+    let (schema_path, paths) = write_files(
+        &temp_dir,
+        &[
+            ("broken.json", r#"{"name": "#),
+            ("valid.json", r#"{"name": "a"}"#),
+        ],
+    );
+
+    let output = cli()
+        .arg("check")
+        .arg("--schema")
+        .arg(&schema_path)
+        .arg("--format")
+        .arg("json")
+        .args(&paths)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("1 of 2 document(s)"))
+        .get_output()
+        .stdout
+        .clone();
+
+    let report: Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(report["valid"], Value::Bool(false));
+    assert_eq!(report["documents"][0]["valid"], Value::Bool(false));
+    assert_eq!(report["documents"][0]["errors"][0]["instance_path"], "");
+    assert_eq!(report["documents"][0]["errors"][0]["keyword"], "parse");
+    assert_eq!(report["documents"][1]["valid"], Value::Bool(true));
+}
@@ -1,5 +1,12 @@
+use assert_cmd::cargo::cargo_bin;
+use deckard::constants;
 use predicates::prelude::*;
 use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
 use tempfile::TempDir;
 
 use crate::support::cli;
@@ -463,3 +470,1998 @@ fn test_convert_with_debug_logging() {
         .success()
         .stderr(predicate::str::contains("DEBUG"));
 }
+
+#[test]
+fn test_ref_to_definition() {
+    cli()
+        .arg("convert")
+        .write_stdin(
+            r##"{
+            "type":"object",
+            "properties":{
+                "device":{"$ref":"#/$defs/Device"}
+            },
+            "$defs":{
+                "Device":{
+                    "type":"object",
+                    "properties":{
+                        "serial":{"type":"string","minLength":8}
+                    }
+                }
+            }
+        }"##,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<span class="type-badge">object</span>"#,
+        ))
+        .stdout(predicate::str::contains(
+            r##"<a class="ref-link" href="#def-Device">Device</a>"##,
+        ))
+        .stdout(predicate::str::contains("serial"))
+        .stdout(predicate::str::contains("minLength: 8"))
+        .stdout(predicate::str::contains(r#"<span class="type-badge">$ref</span>"#).not());
+}
+
+#[test]
+fn test_ref_to_property_pointer() {
+    cli()
+        .arg("convert")
+        .write_stdin(
+            r##"{
+            "type":"object",
+            "properties":{
+                "primary":{"type":"string","format":"email"},
+                "backup":{"$ref":"#/properties/primary","description":"Fallback address"}
+            }
+        }"##,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "<code>#/properties/primary</code>",
+        ))
+        .stdout(predicate::str::contains("Fallback address"))
+        .stdout(predicate::str::contains("format: email"));
+}
+
+#[test]
+fn test_unresolved_ref() {
+    cli()
+        .arg("convert")
+        .write_stdin(r##"{"type":"object","properties":{"id":{"$ref":"#/$defs/Missing"}}}"##)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<span class="type-badge">$ref</span>"#,
+        ))
+        .stdout(predicate::str::contains("<code>#/$defs/Missing</code>"));
+}
+
+#[test]
+fn test_external_ref_relative_to_input() {
+    let temp_dir = TempDir::new().unwrap();
+    let common_dir = temp_dir.path().join("common");
+    fs::create_dir(&common_dir).unwrap();
+
+    fs::write(
+        common_dir.join("device.json"),
+        r##"{
+            "$defs":{
+                "Id":{"type":"string","pattern":"^dev-","description":"Device identifier"},
+                "Location":{
+                    "type":"object",
+                    "properties":{"site":{"$ref":"#/$defs/Site"}}
+                },
+                "Site":{"type":"string","maxLength":32}
+            }
+        }"##,
+    )
+    .unwrap();
+
+    let input_path = temp_dir.path().join("schema.json");
+    fs::write(
+        &input_path,
+        r##"{
+            "type":"object",
+            "properties":{
+                "id":{"$ref":"./common/device.json#/$defs/Id"},
+                "location":{"$ref":"common/device.json#/$defs/Location"}
+            }
+        }"##,
+    )
+    .unwrap();
+
+    cli()
+        .arg("convert")
+        .arg("-i")
+        .arg(&input_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Device identifier"))
+        .stdout(predicate::str::contains("pattern: ^dev-"))
+        .stdout(predicate::str::contains("site"))
+        .stdout(predicate::str::contains("maxLength: 32"))
+        .stdout(predicate::str::contains(
+            "<code>./common/device.json#/$defs/Id</code>",
+        ));
+}
+
+#[test]
+fn test_external_ref_with_id_base() {
+    let temp_dir = TempDir::new().unwrap();
+
+    fs::write(
+        temp_dir.path().join("address.json"),
+        r#"{
+            "$id":"https://example.com/schemas/address.json",
+            "type":"object",
+            "properties":{"street":{"type":"string"}}
+        }"#,
+    )
+    .unwrap();
+
+    let input_path = temp_dir.path().join("schema.json");
+    fs::write(
+        &input_path,
+        r#"{
+            "$id":"https://example.com/schemas/customer.json",
+            "type":"object",
+            "properties":{
+                "address":{"$ref":"address.json"}
+            }
+        }"#,
+    )
+    .unwrap();
+
+    cli()
+        .arg("convert")
+        .arg("-i")
+        .arg(&input_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<span class="type-badge">object</span>"#,
+        ))
+        .stdout(predicate::str::contains("street"));
+}
+
+#[test]
+fn test_external_ref_from_stdin_uses_working_directory() {
+    let temp_dir = TempDir::new().unwrap();
+
+    fs::write(
+        temp_dir.path().join("common.json"),
+        r#"{"$defs":{"Name":{"type":"string","minLength":2}}}"#,
+    )
+    .unwrap();
+
+    cli()
+        .current_dir(temp_dir.path())
+        .arg("convert")
+        .write_stdin(
+            r##"{"type":"object","properties":{"name":{"$ref":"common.json#/$defs/Name"}}}"##,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("minLength: 2"));
+}
+
+#[test]
+fn test_external_ref_missing_file() {
+    cli()
+        .arg("convert")
+        .write_stdin(r#"{"type":"object","properties":{"id":{"$ref":"missing.json"}}}"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<code>missing.json</code>"));
+}
+
+#[test]
+fn test_recursive_ref() {
+    cli()
+        .arg("convert")
+        .write_stdin(
+            r##"{
+            "type":"object",
+            "properties":{
+                "root":{"$ref":"#/$defs/Node"}
+            },
+            "$defs":{
+                "Node":{
+                    "type":"object",
+                    "properties":{
+                        "name":{"type":"string"},
+                        "children":{"type":"array","items":{"$ref":"#/$defs/Node"}}
+                    }
+                }
+            }
+        }"##,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r##"<span class="ref-label">recursive: see</span><a class="ref-link" href="#def-Node">Node</a>"##,
+        ))
+        .stdout(predicate::str::contains("recursive-ref"));
+}
+
+#[test]
+fn test_recursive_root_ref() {
+    let schema = r##"{
+        "$ref":"#/$defs/Node",
+        "$defs":{
+            "Node":{
+                "type":"object",
+                "properties":{
+                    "name":{"type":"string"},
+                    "children":{"type":"array","items":{"$ref":"#/$defs/Node"}}
+                }
+            }
+        }
+    }"##;
+
+    let output = cli().arg("convert").write_stdin(schema).output().unwrap();
+    assert!(output.status.success());
+    let html = String::from_utf8(output.stdout).unwrap();
+    assert!(html.contains(
+        r##"<span class="ref-label">recursive: see</span><a class="ref-link" href="#def-Node">Node</a>"##
+    ));
+    // The root is Node already, so its children are not expanded a level deeper
+    assert!(!html.contains(r#"<span class="ref-label">See</span>"#));
+
+    let output = cli()
+        .args(["convert", "--format", "markdown"])
+        .write_stdin(schema)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let markdown = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        markdown.contains("| `children[]` | `object` | no |  | recursive: see [Node](#def-Node) |")
+    );
+    assert!(!markdown.contains("children[].children"));
+}
+
+#[test]
+fn test_mutually_recursive_external_refs() {
+    let temp_dir = TempDir::new().unwrap();
+
+    fs::write(
+        temp_dir.path().join("a.json"),
+        r#"{"type":"object","properties":{"b":{"$ref":"b.json"}}}"#,
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("b.json"),
+        r#"{"type":"object","properties":{"a":{"$ref":"a.json"}}}"#,
+    )
+    .unwrap();
+
+    cli()
+        .arg("convert")
+        .arg("-i")
+        .arg(temp_dir.path().join("a.json"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<span class="ref-label">recursive: see</span><code>a.json</code>"#,
+        ));
+}
+
+#[test]
+fn test_max_depth() {
+    let schema = r##"{
+        "type":"object",
+        "properties":{
+            "first":{"$ref":"#/$defs/First"}
+        },
+        "$defs":{
+            "First":{"type":"object","properties":{"second":{"$ref":"#/$defs/Second"}}},
+            "Second":{"type":"object","properties":{"third":{"$ref":"#/$defs/Third"}}},
+            "Third":{"type":"string","description":"Deepest level"}
+        }
+    }"##;
+
+    let output = cli()
+        .arg("convert")
+        .arg("--max-depth")
+        .arg("1")
+        .write_stdin(schema)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let output = String::from_utf8(output).unwrap();
+
+    // The property expands First but only links Second, which is still
+    // documented under Definitions
+    let properties = output.split("definitions-section").next().unwrap();
+    assert!(properties.contains("second"));
+    assert!(properties.contains(r##"href="#def-Second""##));
+    assert!(!properties.contains("third"));
+    assert!(output.contains("Deepest level"));
+}
+
+#[test]
+fn test_yaml_schema_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("schema.yaml");
+
+    fs::write(
+        &input_path,
+        "type: object\nrequired: [name]\nproperties:\n  name:\n    type: string\n    minLength: 1\n",
+    )
+    .unwrap();
+
+    cli()
+        .arg("convert")
+        .arg("-i")
+        .arg(&input_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("data-property=\"name\""))
+        .stdout(predicate::str::contains("required-badge"))
+        .stdout(predicate::str::contains("minLength: 1"));
+}
+
+#[test]
+fn test_yaml_schema_stdin_detected() {
+    cli()
+        .arg("convert")
+        .write_stdin("# Device schema\ntype: object\nproperties:\n  serial:\n    type: string\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("data-property=\"serial\""));
+}
+
+#[test]
+fn test_input_format_override() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("schema.txt");
+    fs::write(&input_path, "description: Plain text extension\n").unwrap();
+
+    cli()
+        .arg("convert")
+        .arg("-i")
+        .arg(&input_path)
+        .arg("--input-format")
+        .arg("yaml")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Plain text extension"));
+
+    // JSON is YAML-compatible, but an explicit json format must be valid JSON
+    cli()
+        .arg("convert")
+        .arg("--input-format")
+        .arg("json")
+        .write_stdin("type: object\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("JSON error"));
+}
+
+#[test]
+fn test_invalid_yaml_reports_location() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("schema.yml");
+    fs::write(&input_path, "type: object\nproperties:\n  name: [\n").unwrap();
+
+    cli()
+        .arg("convert")
+        .arg("-i")
+        .arg(&input_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to parse YAML from"))
+        .stderr(predicate::str::contains("line 4 column 1"));
+
+    cli()
+        .arg("convert")
+        .write_stdin("type: object\nproperties: {\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("YAML error"))
+        .stderr(predicate::str::contains("line 3 column 1"));
+}
+
+#[test]
+fn test_yaml_external_ref() {
+    let temp_dir = TempDir::new().unwrap();
+
+    fs::write(
+        temp_dir.path().join("common.yaml"),
+        "$defs:\n  Id:\n    type: integer\n    minimum: 1\n",
+    )
+    .unwrap();
+
+    let input_path = temp_dir.path().join("schema.yaml");
+    fs::write(
+        &input_path,
+        "type: object\nproperties:\n  id:\n    $ref: common.yaml#/$defs/Id\n",
+    )
+    .unwrap();
+
+    cli()
+        .arg("convert")
+        .arg("-i")
+        .arg(&input_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<span class="type-badge">integer</span>"#,
+        ))
+        .stdout(predicate::str::contains("min: 1"));
+}
+
+#[test]
+fn test_standalone_document() {
+    cli()
+        .arg("convert")
+        .arg("--standalone")
+        .write_stdin(
+            r#"{"title":"Device Config","type":"object","properties":{"id":{"type":"string"}}}"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("<!DOCTYPE html>"))
+        .stdout(predicate::str::contains("<title>Device Config</title>"))
+        .stdout(predicate::str::contains("<style>"))
+        .stdout(predicate::str::contains(".schema-container"))
+        .stdout(predicate::str::contains(
+            r#"<body><div class="schema-container">"#,
+        ))
+        .stdout(predicate::str::contains("</body></html>"));
+}
+
+#[test]
+fn test_standalone_linked_stylesheet() {
+    cli()
+        .arg("convert")
+        .arg("--stylesheet")
+        .arg("https://example.com/docs.css")
+        .write_stdin(r#"{"type":"object"}"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<!DOCTYPE html>"))
+        .stdout(predicate::str::contains(
+            r#"<link rel="stylesheet" href="https://example.com/docs.css">"#,
+        ))
+        .stdout(predicate::str::contains("<style>").not());
+}
+
+#[test]
+fn test_standalone_inline_stylesheet() {
+    let temp_dir = TempDir::new().unwrap();
+    let css_path = temp_dir.path().join("custom.css");
+    fs::write(&css_path, ".property-name { color: rebeccapurple; }").unwrap();
+
+    cli()
+        .arg("convert")
+        .arg("--inline-stylesheet")
+        .arg(&css_path)
+        .write_stdin(r#"{"type":"object"}"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<!DOCTYPE html>"))
+        .stdout(predicate::str::contains("color: rebeccapurple;"))
+        .stdout(predicate::str::contains(".schema-container {").not());
+}
+
+#[test]
+fn test_standalone_missing_inline_stylesheet() {
+    cli()
+        .arg("convert")
+        .arg("--inline-stylesheet")
+        .arg("/nonexistent/custom.css")
+        .write_stdin(r#"{"type":"object"}"#)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Failed to read stylesheet '/nonexistent/custom.css'",
+        ));
+}
+
+#[test]
+fn test_stylesheet_options_conflict() {
+    cli()
+        .arg("convert")
+        .arg("--stylesheet")
+        .arg("docs.css")
+        .arg("--inline-stylesheet")
+        .arg("docs.css")
+        .write_stdin(r#"{"type":"object"}"#)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_no_standalone_overrides_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("deckard.yaml");
+    fs::write(&config_path, "convert:\n  standalone: true\n").unwrap();
+
+    cli()
+        .arg("-C")
+        .arg(&config_path)
+        .arg("convert")
+        .write_stdin(r#"{"type":"object"}"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<!DOCTYPE html>"));
+
+    cli()
+        .arg("-C")
+        .arg(&config_path)
+        .arg("convert")
+        .arg("--no-standalone")
+        .write_stdin(r#"{"type":"object"}"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<!DOCTYPE html>").not());
+}
+
+#[test]
+fn test_markdown_properties_table() {
+    cli()
+        .arg("convert")
+        .arg("--format")
+        .arg("markdown")
+        .write_stdin(
+            r#"{
+                "description": "A user",
+                "type": "object",
+                "required": ["name"],
+                "properties": {
+                    "name": {"type": "string", "minLength": 1, "description": "Full name"},
+                    "role": {"type": "string", "enum": ["admin", "user"], "default": "user"},
+                    "profile": {
+                        "type": "object",
+                        "properties": {"bio": {"type": "string", "examples": ["Hi"]}}
+                    },
+                    "tags": {"type": "array", "items": {"type": "string"}}
+                }
+            }"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("A user\n\n## Properties\n"))
+        .stdout(predicate::str::contains(
+            "| Property | Type | Required | Description | Details |",
+        ))
+        .stdout(predicate::str::contains(
+            "| `name` | `string` | yes | Full name | minLength: 1 |",
+        ))
+        .stdout(predicate::str::contains(
+            "| `role` | `string` | no |  | Possible values: `\"admin\"`, `\"user\"`<br>Default: `\"user\"` |",
+        ))
+        .stdout(predicate::str::contains(
+            "| `profile.bio` | `string` | no |  | Examples: `Hi` |",
+        ))
+        .stdout(predicate::str::contains("| `tags[]` | `string` | no |  |  |"))
+        .stdout(predicate::str::contains("schema-container").not());
+}
+
+#[test]
+fn test_markdown_definitions_and_refs() {
+    cli()
+        .arg("convert")
+        .arg("-f")
+        .arg("markdown")
+        .write_stdin(
+            r##"{
+                "type": "object",
+                "properties": {"root": {"$ref": "#/$defs/Node"}},
+                "oneOf": [{"type": "object"}, {"type": "null"}],
+                "$defs": {
+                    "Node": {
+                        "type": "object",
+                        "properties": {"next": {"$ref": "#/$defs/Node"}}
+                    }
+                }
+            }"##,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "| `root` | `object` | no |  | See [Node](#def-Node) |",
+        ))
+        .stdout(predicate::str::contains(
+            "| `root.next` | `object` | no |  | recursive: see [Node](#def-Node) |",
+        ))
+        .stdout(predicate::str::contains("### One Of\n\n#### Option 1\n"))
+        .stdout(predicate::str::contains(
+            "## Definitions\n\n<a id=\"def-Node\"></a>\n\n### Node\n",
+        ));
+}
+
+#[test]
+fn test_markdown_escaping() {
+    cli()
+        .arg("convert")
+        .arg("--format")
+        .arg("markdown")
+        .write_stdin(
+            r#"{"type":"object","properties":{"a":{"type":"string","description":"x | <b>y</b>\nz","pattern":"^a|b$"}}}"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "| `a` | `string` | no | x \\| &lt;b&gt;y&lt;/b&gt;<br>z | pattern: ^a\\|b$ |",
+        ));
+}
+
+#[test]
+fn test_markdown_format_from_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("deckard.yaml");
+    fs::write(&config_path, "convert:\n  format: markdown\n").unwrap();
+
+    cli()
+        .arg("-C")
+        .arg(&config_path)
+        .arg("convert")
+        .write_stdin(r#"{"type":"object","properties":{"a":{"type":"string"}}}"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## Properties"));
+
+    cli()
+        .arg("-C")
+        .arg(&config_path)
+        .arg("convert")
+        .arg("--format")
+        .arg("html")
+        .write_stdin(r#"{"type":"object","properties":{"a":{"type":"string"}}}"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("schema-container"));
+}
+
+#[test]
+fn test_titles_rendered() {
+    cli()
+        .arg("convert")
+        .write_stdin(
+            r#"{"title":"Device <Config>","type":"object","properties":{"id":{"title":"Identifier","type":"string"}}}"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<h1 class="schema-title">Device &lt;Config&gt;</h1>"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<span class="property-name">id</span><span class="property-title">Identifier</span>"#,
+        ));
+}
+
+#[test]
+fn test_no_titles() {
+    cli()
+        .arg("convert")
+        .arg("--no-titles")
+        .arg("--standalone")
+        .write_stdin(
+            r#"{"title":"Device","type":"object","properties":{"id":{"title":"Identifier","type":"string"}}}"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<title>Device</title>"))
+        .stdout(predicate::str::contains(r#"class="schema-title""#).not())
+        .stdout(predicate::str::contains(r#"class="property-title""#).not());
+}
+
+#[test]
+fn test_titles_from_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("deckard.yaml");
+    fs::write(&config_path, "convert:\n  titles: false\n").unwrap();
+    let schema = r#"{"title":"Device","type":"object"}"#;
+
+    cli()
+        .arg("-C")
+        .arg(&config_path)
+        .arg("convert")
+        .write_stdin(schema)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("schema-title").not());
+
+    cli()
+        .arg("-C")
+        .arg(&config_path)
+        .arg("convert")
+        .arg("--titles")
+        .write_stdin(schema)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("schema-title"));
+}
+
+#[test]
+fn test_markdown_titles() {
+    let schema = r#"{"title":"Device","type":"object","properties":{"id":{"title":"Identifier","type":"string"}}}"#;
+
+    cli()
+        .arg("convert")
+        .arg("--format")
+        .arg("markdown")
+        .write_stdin(schema)
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("# Device\n\n## Properties\n"))
+        .stdout(predicate::str::contains(
+            "| `id`<br>Identifier | `string` | no |  |  |",
+        ));
+
+    cli()
+        .arg("convert")
+        .arg("--format")
+        .arg("markdown")
+        .arg("--no-titles")
+        .write_stdin(schema)
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("## Properties\n"))
+        .stdout(predicate::str::contains("Identifier").not());
+}
+
+#[test]
+fn test_object_applicators() {
+    cli()
+        .arg("convert")
+        .write_stdin(
+            r#"{
+                "type": "object",
+                "properties": {
+                    "labels": {
+                        "type": "object",
+                        "patternProperties": {"^x-": {"type": "integer"}},
+                        "additionalProperties": {"type": "string"},
+                        "propertyNames": {"pattern": "^[a-z-]+$"}
+                    }
+                },
+                "unevaluatedProperties": false
+            }"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<div class="pattern-properties"><div class="applicator-label">Pattern properties:</div><div class="pattern-property"><code class="property-pattern">^x-</code><span class="type-badge">integer</span>"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<div class="additional-properties"><div class="applicator-label">Additional properties:</div><span class="type-badge">string</span>"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<div class="property-names"><div class="applicator-label">Property names:</div><div class="schema-details depth-1"><div class="constraints"><span class="constraint">pattern: ^[a-z-]+$</span>"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<div class="applicators-section"><div class="unevaluated-properties"><div class="applicator-label">Unevaluated properties:</div><div class="schema-details depth-0"><span class="boolean-schema">not allowed</span>"#,
+        ));
+}
+
+#[test]
+fn test_array_applicators() {
+    cli()
+        .arg("convert")
+        .write_stdin(
+            r#"{
+                "type": "array",
+                "prefixItems": [{"type": "string"}, {"type": "number"}],
+                "contains": {"type": "string"},
+                "minContains": 1,
+                "maxContains": 3
+            }"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<div class="prefix-items"><div class="applicator-label">Prefix items:</div><div class="prefix-item"><div class="item-index">[0]</div><span class="type-badge">string</span>"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<div class="item-index">[1]</div><span class="type-badge">number</span>"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<div class="contains"><div class="applicator-label">Contains:</div><div class="constraints"><span class="constraint">minContains: 1</span><span class="constraint">maxContains: 3</span></div><span class="type-badge">string</span>"#,
+        ));
+}
+
+#[test]
+fn test_dependent_applicators() {
+    cli()
+        .arg("convert")
+        .write_stdin(
+            r#"{
+                "type": "object",
+                "properties": {"card": {"type": "string"}},
+                "dependentRequired": {"card": ["billing", "expiry"]},
+                "dependentSchemas": {"card": {"type": "object", "properties": {"cvv": {"type": "string"}}}}
+            }"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<div class="dependent-required"><div class="applicator-label">Dependent required:</div><div class="dependency"><code class="dependency-property">card</code><span class="dependency-label">requires</span><code>billing</code><code>expiry</code></div></div>"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<div class="dependent-schemas"><div class="applicator-label">Dependent schemas:</div><div class="dependency"><code class="dependency-property">card</code><span class="dependency-label">is present</span><span class="type-badge">object</span>"#,
+        ))
+        .stdout(predicate::str::contains(r#"data-property="cvv""#));
+}
+
+#[test]
+fn test_markdown_applicators() {
+    cli()
+        .arg("convert")
+        .arg("--format")
+        .arg("markdown")
+        .write_stdin(
+            r#"{
+                "type": "object",
+                "properties": {
+                    "pair": {"type": "array", "prefixItems": [{"type": "string"}], "items": false},
+                    "labels": {"type": "object", "additionalProperties": {"type": "string"}}
+                },
+                "dependentRequired": {"card": ["billing"]}
+            }"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "| `labels.*` | `string` | no |  | Additional properties |",
+        ))
+        .stdout(predicate::str::contains(
+            "| `pair[]` |  | no |  | not allowed |",
+        ))
+        .stdout(predicate::str::contains(
+            "| `pair[0]` | `string` | no |  | Prefix item |",
+        ))
+        .stdout(predicate::str::contains("- `card` requires `billing`"));
+}
+
+#[test]
+fn test_conditional() {
+    cli()
+        .arg("convert")
+        .write_stdin(
+            r#"{
+                "type": "object",
+                "properties": {"model": {"type": "string"}},
+                "if": {"properties": {"model": {"enum": ["X200"]}}},
+                "then": {"properties": {"channels": {"type": "integer"}}, "required": ["channels"]},
+                "else": {"properties": {"channels": false}}
+            }"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<div class="conditional-section"><h2>Conditional</h2><div class="conditional"><div class="condition"><div class="conditional-label">If:</div><div class="schema-details depth-0"><div class="nested-properties"><div class="property depth-0" data-property="model">"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<div class="then-branch"><div class="conditional-label">Then:</div><div class="schema-details depth-0"><div class="nested-properties"><div class="property depth-0" data-property="channels"><div class="property-header"><span class="property-name">channels</span><span class="type-badge">integer</span><span class="required-badge">required</span>"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<div class="else-branch"><div class="conditional-label">Else:</div>"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<span class="boolean-schema">not allowed</span>"#,
+        ));
+}
+
+#[test]
+fn test_nested_conditional() {
+    cli()
+        .arg("convert")
+        .write_stdin(
+            r#"{
+                "type": "object",
+                "properties": {
+                    "port": {
+                        "type": "object",
+                        "if": {"properties": {"kind": {"enum": ["serial"]}}},
+                        "then": {"properties": {"baud": {"type": "integer"}}}
+                    }
+                }
+            }"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<div class="property depth-0" data-property="port"><div class="property-header"><span class="property-name">port</span><span class="type-badge">object</span></div><div class="schema-details depth-1"><div class="conditional"><div class="condition">"#,
+        ))
+        .stdout(predicate::str::contains(r#"data-property="baud""#))
+        .stdout(predicate::str::contains("else-branch").not());
+}
+
+#[test]
+fn test_then_without_if() {
+    cli()
+        .arg("convert")
+        .write_stdin(r#"{"type": "object", "then": {"properties": {"a": {"type": "string"}}}}"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("conditional").not());
+}
+
+#[test]
+fn test_markdown_conditional() {
+    cli()
+        .arg("convert")
+        .arg("--format")
+        .arg("markdown")
+        .write_stdin(
+            r#"{
+                "type": "object",
+                "properties": {
+                    "port": {
+                        "type": "object",
+                        "if": {"properties": {"kind": {"enum": ["serial"]}}},
+                        "then": {"properties": {"baud": {"type": "integer"}}, "required": ["baud"]}
+                    }
+                },
+                "if": {"properties": {"model": {"enum": ["X200"]}}},
+                "else": {"properties": {"channels": false}}
+            }"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("| `port` |  | no |  | If |"))
+        .stdout(predicate::str::contains("| `port` |  | no |  | Then |"))
+        .stdout(predicate::str::contains(
+            "| `port.baud` | `integer` | yes |  |  |",
+        ))
+        .stdout(predicate::str::contains("## Conditional\n\n### If\n\n"))
+        .stdout(predicate::str::contains("### Else\n\n"))
+        .stdout(predicate::str::contains(
+            "| `channels` |  | no |  | not allowed |",
+        ));
+}
+
+#[test]
+fn test_json_schema_with_more_constraints() {
+    cli()
+        .arg("convert")
+        .write_stdin(
+            r#"{
+                "type": "object",
+                "properties": {
+                    "kind": {"const": "device"},
+                    "step": {"type": "number", "multipleOf": 0.5},
+                    "labels": {"type": "object", "minProperties": 1, "maxProperties": 8},
+                    "name": {"type": "string", "not": {"const": ""}}
+                }
+            }"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("const: &quot;device&quot;"))
+        .stdout(predicate::str::contains("multipleOf: 0.5"))
+        .stdout(predicate::str::contains("minProperties: 1"))
+        .stdout(predicate::str::contains("maxProperties: 8"))
+        .stdout(predicate::str::contains(
+            r#"<div class="not-schema"><div class="applicator-label">Not:</div><div class="schema-details depth-1"><div class="constraints"><span class="constraint">const: &quot;&quot;</span>"#,
+        ));
+}
+
+#[test]
+fn test_annotation_badges() {
+    cli()
+        .arg("convert")
+        .write_stdin(
+            r##"{
+                "type": "object",
+                "properties": {
+                    "legacyId": {"type": "string", "deprecated": true, "readOnly": true},
+                    "password": {"type": "string", "writeOnly": true},
+                    "owner": {"$ref": "#/$defs/User", "deprecated": true}
+                },
+                "$defs": {"User": {"type": "string"}}
+            }"##,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<span class="property-name">legacyId</span><span class="type-badge">string</span><span class="deprecated-badge">deprecated</span><span class="read-only-badge">read-only</span></div>"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<span class="property-name">password</span><span class="type-badge">string</span><span class="write-only-badge">write-only</span></div>"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<span class="property-name">owner</span><span class="type-badge">string</span><span class="deprecated-badge">deprecated</span></div>"#,
+        ));
+}
+
+#[test]
+fn test_markdown_annotations() {
+    cli()
+        .arg("convert")
+        .arg("--format")
+        .arg("markdown")
+        .write_stdin(
+            r#"{
+                "type": "object",
+                "required": ["password"],
+                "properties": {
+                    "legacyId": {"type": "string", "deprecated": true, "minLength": 4},
+                    "password": {"type": "string", "writeOnly": true, "not": {"const": ""}}
+                }
+            }"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "| `legacyId` | `string` | no |  | **Deprecated**<br>minLength: 4 |",
+        ))
+        .stdout(predicate::str::contains(
+            "| `password` | `string` | yes |  | **Write-only** |",
+        ))
+        .stdout(predicate::str::contains(
+            "| `password` |  | no |  | Not<br>const: \"\" |",
+        ));
+}
+
+#[test]
+fn test_exclusive_bounds() {
+    cli()
+        .arg("convert")
+        .write_stdin(
+            r#"{
+                "type": "object",
+                "properties": {
+                    "ratio": {"type": "number", "exclusiveMinimum": 0, "maximum": 1},
+                    "offset": {"type": "number", "minimum": -5, "exclusiveMaximum": 5}
+                }
+            }"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"data-property="ratio"><div class="property-header"><span class="property-name">ratio</span><span class="type-badge">number</span></div><div class="schema-details depth-1"><div class="constraints"><span class="constraint">min: &gt; 0</span><span class="constraint">max: 1</span></div>"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<span class="constraint">min: -5</span><span class="constraint">max: &lt; 5</span>"#,
+        ));
+}
+
+#[test]
+fn test_draft4_exclusive_bounds() {
+    cli()
+        .arg("convert")
+        .write_stdin(
+            r#"{
+                "$schema": "http://json-schema.org/draft-04/schema#",
+                "type": "object",
+                "properties": {
+                    "ratio": {"type": "number", "minimum": 0, "exclusiveMinimum": true, "maximum": 1, "exclusiveMaximum": false}
+                }
+            }"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<div class="constraints"><span class="constraint">min: &gt; 0</span><span class="constraint">max: 1</span></div>"#,
+        ))
+        .stdout(predicate::str::contains("exclusive").not());
+}
+
+#[test]
+fn test_boolean_exclusive_bounds_without_schema() {
+    cli()
+        .arg("convert")
+        .write_stdin(
+            r#"{
+                "type": "object",
+                "properties": {
+                    "ratio": {"type": "number", "minimum": 0, "exclusiveMinimum": true, "maximum": 1, "exclusiveMaximum": true}
+                }
+            }"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<div class="constraints"><span class="constraint">min: &gt; 0</span><span class="constraint">max: &lt; 1</span></div>"#,
+        ));
+}
+
+#[test]
+fn test_union_type_arrays() {
+    cli()
+        .arg("convert")
+        .write_stdin(
+            r#"{
+                "type": ["object", "null"],
+                "properties": {
+                    "owner": {"type": ["object", "null"], "properties": {"id": {"type": "integer"}}},
+                    "tags": {"type": ["array", "null"], "items": {"type": "string"}},
+                    "name": {"type": ["string", "null"], "properties": {"ignored": {"type": "string"}}}
+                }
+            }"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#"<span class="type-badge">object | null</span>"#))
+        .stdout(predicate::str::contains(r#"data-property="id""#))
+        .stdout(predicate::str::contains(
+            r#"<div class="array-items"><div class="array-label">Items:</div>"#,
+        ))
+        .stdout(predicate::str::contains(r#"data-property="ignored""#).not());
+}
+
+#[test]
+fn test_inferred_types() {
+    cli()
+        .arg("convert")
+        .write_stdin(
+            r#"{
+                "properties": {
+                    "owner": {"properties": {"id": {"type": "integer"}}},
+                    "tags": {"items": {"type": "string"}, "uniqueItems": true}
+                }
+            }"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#"<div class="properties-section">"#))
+        .stdout(predicate::str::contains(
+            r#"<div class="property depth-1" data-property="id">"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<div class="array-items"><div class="array-label">Items:</div><div class="schema-details depth-1">"#,
+        ))
+        // The type isn't shown, such schemas also accept other types
+        .stdout(predicate::str::contains(r#"<span class="type-badge">object</span>"#).not());
+}
+
+#[test]
+fn test_markdown_inferred_types() {
+    cli()
+        .arg("convert")
+        .arg("--format")
+        .arg("markdown")
+        .write_stdin(
+            r#"{
+                "items": {
+                    "type": ["object", "null"],
+                    "properties": {"tags": {"items": {"type": "string"}}}
+                }
+            }"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## Array Items\n\nType: `object"))
+        .stdout(predicate::str::contains("| `tags` |  | no |  |  |"))
+        .stdout(predicate::str::contains(
+            "| `tags[]` | `string` | no |  |  |",
+        ));
+}
+
+const ALL_OF_SCHEMA: &str = r##"{
+    "allOf": [
+        {"$ref": "#/$defs/Base"},
+        {
+            "type": "object",
+            "required": ["id"],
+            "properties": {"model": {"type": "string"}, "id": {"minLength": 3}}
+        }
+    ],
+    "$defs": {
+        "Base": {
+            "type": "object",
+            "required": ["created"],
+            "properties": {"id": {"type": "string"}, "created": {"type": "string"}}
+        }
+    }
+}"##;
+
+#[test]
+fn test_all_of_options_by_default() {
+    cli()
+        .arg("convert")
+        .write_stdin(ALL_OF_SCHEMA)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<h3>All Of</h3>"))
+        .stdout(predicate::str::contains("<h4>Option 2</h4>"))
+        .stdout(predicate::str::contains("property-origin").not());
+}
+
+#[test]
+fn test_merge_all_of() {
+    cli()
+        .arg("convert")
+        .arg("--merge-all-of")
+        .write_stdin(ALL_OF_SCHEMA)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("All Of").not())
+        .stdout(predicate::str::contains(
+            r#"<div class="property depth-0" data-property="id"><div class="property-header"><span class="property-name">id</span><span class="type-badge">string</span><span class="required-badge">required</span><span class="property-origin">from Base</span></div><div class="schema-details depth-1"><div class="constraints"><span class="constraint">minLength: 3</span></div>"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<span class="property-name">created</span><span class="type-badge">string</span><span class="required-badge">required</span><span class="property-origin">from Base</span>"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<span class="property-name">model</span><span class="type-badge">string</span><span class="property-origin">from allOf/1</span>"#,
+        ));
+}
+
+#[test]
+fn test_merge_nested_all_of() {
+    cli()
+        .arg("convert")
+        .arg("--format")
+        .arg("markdown")
+        .arg("--merge-all-of")
+        .write_stdin(
+            r##"{
+                "type": "object",
+                "properties": {
+                    "owner": {
+                        "allOf": [
+                            {"$ref": "#/$defs/Named"},
+                            {"title": "Contact", "properties": {"email": {"type": "string"}}}
+                        ]
+                    }
+                },
+                "$defs": {"Named": {"type": "object", "properties": {"name": {"type": "string"}}}}
+            }"##,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "| `owner` | `object` | no |  |  |",
+        ))
+        .stdout(predicate::str::contains(
+            "| `owner.name` | `string` | no |  | From `Named` |",
+        ))
+        .stdout(predicate::str::contains(
+            "| `owner.email` | `string` | no |  | From `Contact` |",
+        ));
+}
+
+#[test]
+fn test_merge_all_of_from_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.yaml");
+    fs::write(&config_path, "convert:\n  merge_all_of: true\n").unwrap();
+
+    cli()
+        .arg("--config")
+        .arg(&config_path)
+        .arg("convert")
+        .write_stdin(ALL_OF_SCHEMA)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("from Base"));
+
+    cli()
+        .arg("--config")
+        .arg(&config_path)
+        .arg("convert")
+        .arg("--no-merge-all-of")
+        .write_stdin(ALL_OF_SCHEMA)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<h3>All Of</h3>"));
+}
+
+const UNION_SCHEMA: &str = r##"{
+    "oneOf": [
+        {"type": "object", "properties": {"kind": {"const": "sensor"}, "unit": {"type": "string"}}},
+        {"$ref": "#/$defs/Actuator"},
+        {"type": "object", "properties": {"kind": {"enum": ["relay"]}}}
+    ],
+    "$defs": {
+        "Actuator": {"type": "object", "properties": {"kind": {"const": "actuator"}}}
+    }
+}"##;
+
+#[test]
+fn test_discriminated_union() {
+    cli()
+        .arg("convert")
+        .write_stdin(UNION_SCHEMA)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<h3>One Of</h3><div class="discriminator"><span class="discriminator-label">Discriminator:</span><code>kind</code></div><div class="compound-options"><div class="compound-option"><h4>sensor</h4>"#,
+        ))
+        .stdout(predicate::str::contains("<h4>actuator</h4>"))
+        .stdout(predicate::str::contains("<h4>relay</h4>"))
+        .stdout(predicate::str::contains("Option 1").not());
+}
+
+#[test]
+fn test_option_labels_from_title_and_ref() {
+    cli()
+        .arg("convert")
+        .write_stdin(
+            r##"{
+                "anyOf": [
+                    {"title": "Plain text", "type": "string"},
+                    {"$ref": "#/$defs/Point"},
+                    {"type": "null"}
+                ],
+                "$defs": {"Point": {"type": "array"}}
+            }"##,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("discriminator").not())
+        .stdout(predicate::str::contains("<h4>Plain text</h4>"))
+        .stdout(predicate::str::contains("<h4>Point</h4>"))
+        .stdout(predicate::str::contains("<h4>Option 3</h4>"));
+}
+
+#[test]
+fn test_openapi_discriminator() {
+    cli()
+        .arg("convert")
+        .write_stdin(
+            r##"{
+                "oneOf": [{"$ref": "#/$defs/Cat"}, {"$ref": "#/$defs/Dog"}],
+                "discriminator": {"propertyName": "petType", "mapping": {"kitty": "#/$defs/Cat"}},
+                "$defs": {"Cat": {"type": "object"}, "Dog": {"type": "object"}}
+            }"##,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<code>petType</code>"))
+        .stdout(predicate::str::contains("<h4>kitty</h4>"))
+        .stdout(predicate::str::contains("<h4>Dog</h4>"));
+}
+
+#[test]
+fn test_compound_tabs() {
+    cli()
+        .arg("convert")
+        .arg("--tabs")
+        .write_stdin(UNION_SCHEMA)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<div class="compound-options compound-tabs"><input type="radio" class="tab-input" name="tab-oneOf" id="tab-oneOf-1" checked><label class="tab-label" for="tab-oneOf-1">sensor</label><div class="compound-option tab-panel"><div class="schema-details depth-0">"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<input type="radio" class="tab-input" name="tab-oneOf" id="tab-oneOf-2"><label class="tab-label" for="tab-oneOf-2">actuator</label>"#,
+        ))
+        .stdout(predicate::str::contains("<h4>").not());
+}
+
+const NESTED_UNION_SCHEMA: &str = r##"{
+    "type": "object",
+    "properties": {
+        "payload": {
+            "oneOf": [
+                {"type": "object", "properties": {"kind": {"const": "reading"}, "value": {"type": "number"}}},
+                {"$ref": "#/$defs/Command"}
+            ]
+        }
+    },
+    "$defs": {
+        "Command": {"type": "object", "properties": {"kind": {"const": "command"}, "action": {"type": "string"}}}
+    }
+}"##;
+
+#[test]
+fn test_nested_compound() {
+    cli()
+        .arg("convert")
+        .write_stdin(NESTED_UNION_SCHEMA)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<div class="property depth-0" data-property="payload"><div class="property-header"><span class="property-name">payload</span><span class="type-badge">oneOf</span></div><div class="schema-details depth-1"><div class="compound-schema"><h4>One Of</h4><div class="discriminator"><span class="discriminator-label">Discriminator:</span><code>kind</code></div><div class="compound-options"><div class="compound-option"><h5>reading</h5><div class="schema-details depth-1"><div class="nested-properties"><div class="property depth-1" data-property="kind">"#,
+        ))
+        .stdout(predicate::str::contains(
+            r##"<h5>command</h5><div class="schema-details depth-1"><div class="schema-ref"><span class="ref-label">See</span><a class="ref-link" href="#def-Command">Command</a></div>"##,
+        ));
+
+    // Every tab group of the page has its own ids
+    cli()
+        .arg("convert")
+        .arg("--tabs")
+        .write_stdin(NESTED_UNION_SCHEMA)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<input type="radio" class="tab-input" name="tab-1-oneOf" id="tab-1-oneOf-2"><label class="tab-label" for="tab-1-oneOf-2">command</label>"#,
+        ));
+}
+
+#[test]
+fn test_nested_compound_markdown() {
+    cli()
+        .arg("convert")
+        .arg("--format")
+        .arg("markdown")
+        .write_stdin(NESTED_UNION_SCHEMA)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "| `payload` | `oneOf` | no |  |  |\n\
+             | `payload` | `object` | no |  | One Of: reading<br>Discriminator: `kind` |\n\
+             | `payload.kind` |  | no |  | const: \"reading\" |\n\
+             | `payload.value` | `number` | no |  |  |\n\
+             | `payload` | `object` | no |  | One Of: command<br>Discriminator: `kind`<br>See [Command](#def-Command) |\n",
+        ));
+}
+
+#[test]
+fn test_compound_tabs_from_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.yaml");
+    fs::write(&config_path, "convert:\n  tabs: true\n").unwrap();
+
+    cli()
+        .arg("--config")
+        .arg(&config_path)
+        .arg("convert")
+        .write_stdin(UNION_SCHEMA)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("compound-tabs"));
+
+    cli()
+        .arg("--config")
+        .arg(&config_path)
+        .arg("convert")
+        .arg("--no-tabs")
+        .write_stdin(UNION_SCHEMA)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("compound-tabs").not());
+}
+
+#[test]
+fn test_markdown_discriminated_union() {
+    cli()
+        .arg("convert")
+        .arg("--format")
+        .arg("markdown")
+        .write_stdin(UNION_SCHEMA)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "### One Of\n\nDiscriminator: `kind`\n\n#### sensor\n\n",
+        ))
+        .stdout(predicate::str::contains(
+            "#### actuator\n\nSee [Actuator](#def-Actuator)",
+        ));
+}
+
+/// A directory of schemas, one of them malformed
+fn write_schema_tree(dir: &TempDir) -> PathBuf {
+    let schemas = dir.path().join("schemas");
+    fs::create_dir_all(schemas.join("devices")).unwrap();
+    fs::write(
+        schemas.join("root.json"),
+        r#"{"title":"Root","type":"object","properties":{"id":{"type":"string"}}}"#,
+    )
+    .unwrap();
+    fs::write(
+        schemas.join("devices/light.yaml"),
+        "title: Light\ntype: object\nproperties:\n  on:\n    type: boolean\n",
+    )
+    .unwrap();
+    fs::write(schemas.join("devices/notes.txt"), "not a schema").unwrap();
+    schemas
+}
+
+#[test]
+fn test_convert_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    let schemas = write_schema_tree(&temp_dir);
+    let output_dir = temp_dir.path().join("out");
+
+    cli()
+        .arg("-vv")
+        .arg("convert")
+        .arg("-i")
+        .arg(&schemas)
+        .arg("-o")
+        .arg(&output_dir)
+        .arg("--jobs")
+        .arg("2")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("Converted 2 of 2 schema(s)"));
+
+    let root = fs::read_to_string(output_dir.join("root.html")).unwrap();
+    assert!(root.contains(r#"<h1 class="schema-title">Root</h1>"#));
+    let light = fs::read_to_string(output_dir.join("devices/light.html")).unwrap();
+    assert!(light.contains(r#"<span class="property-name">on</span>"#));
+    assert!(!output_dir.join("devices/notes.html").exists());
+}
+
+#[test]
+fn test_convert_pattern() {
+    let temp_dir = TempDir::new().unwrap();
+    let schemas = write_schema_tree(&temp_dir);
+    let output_dir = temp_dir.path().join("out");
+
+    cli()
+        .arg("-vv")
+        .arg("convert")
+        .arg("-i")
+        .arg(schemas.join("**/*.yaml"))
+        .arg("-o")
+        .arg(&output_dir)
+        .arg("--format")
+        .arg("markdown")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("Converted 1 of 1 schema(s)"));
+
+    let light = fs::read_to_string(output_dir.join("devices/light.md")).unwrap();
+    assert!(light.starts_with("# Light\n"));
+    assert!(!output_dir.join("root.md").exists());
+}
+
+#[test]
+fn test_convert_directory_continues_past_failures() {
+    let temp_dir = TempDir::new().unwrap();
+    let schemas = write_schema_tree(&temp_dir);
+    fs::write(schemas.join("broken.json"), "{ not json").unwrap();
+    let output_dir = temp_dir.path().join("out");
+
+    cli()
+        .arg("-vv")
+        .arg("convert")
+        .arg("-i")
+        .arg(&schemas)
+        .arg("-o")
+        .arg(&output_dir)
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("Converted 2 of 3 schema(s)"))
+        .stderr(predicate::str::contains("1 schema(s) failed to convert:"))
+        .stderr(predicate::str::contains(
+            "broken.json: Failed to parse JSON",
+        ));
+
+    assert!(output_dir.join("root.html").exists());
+    assert!(output_dir.join("devices/light.html").exists());
+}
+
+#[test]
+fn test_convert_directory_output_collision() {
+    let temp_dir = TempDir::new().unwrap();
+    let schemas = write_schema_tree(&temp_dir);
+    fs::write(schemas.join("devices/light.json"), r#"{"title":"Lamp"}"#).unwrap();
+    let output_dir = temp_dir.path().join("out");
+
+    cli()
+        .arg("convert")
+        .arg("-i")
+        .arg(&schemas)
+        .arg("-o")
+        .arg(&output_dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("light.json' and '"))
+        .stderr(predicate::str::contains(
+            "light.yaml' would both be written to '",
+        ));
+
+    assert!(!output_dir.exists());
+}
+
+#[test]
+fn test_convert_directory_needs_output() {
+    let temp_dir = TempDir::new().unwrap();
+    let schemas = write_schema_tree(&temp_dir);
+
+    cli()
+        .arg("convert")
+        .arg("-i")
+        .arg(&schemas)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("needs an --output directory"));
+}
+
+#[test]
+fn test_watch_needs_input_file() {
+    cli()
+        .arg("convert")
+        .arg("--watch")
+        .write_stdin(r#"{"type":"object"}"#)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--watch needs an --input file."));
+
+    let temp_dir = TempDir::new().unwrap();
+    let schemas = write_schema_tree(&temp_dir);
+    cli()
+        .arg("convert")
+        .arg("-i")
+        .arg(&schemas)
+        .arg("-o")
+        .arg(temp_dir.path().join("out"))
+        .arg("--watch")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--watch only supports a single input file.",
+        ));
+}
+
+/// Wait for the file at `path` to exist and contain `text`
+fn wait_for_content(path: &Path, text: &str) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while Instant::now() < deadline {
+        if fs::read_to_string(path).is_ok_and(|content| content.contains(text)) {
+            return;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    panic!("'{}' never contained '{}'", path.display(), text);
+}
+
+#[test]
+fn test_watch_regenerates() {
+    let temp_dir = TempDir::new().unwrap();
+    let schema_path = temp_dir.path().join("schema.json");
+    let common_path = temp_dir.path().join("common.json");
+    let output_path = temp_dir.path().join("out.html");
+    let schema = |description: &str| {
+        format!(
+            r#"{{"description": "{}", "properties": {{"id": {{"$ref": "common.json"}}}}}}"#,
+            description
+        )
+    };
+    fs::write(&schema_path, schema("First")).unwrap();
+    fs::write(&common_path, r#"{"description": "Common"}"#).unwrap();
+
+    let mut child = Command::new(cargo_bin(constants::CLI_BIN))
+        .current_dir(temp_dir.path())
+        .args([
+            "-vv",
+            "convert",
+            "-i",
+            "schema.json",
+            "-o",
+            "out.html",
+            "-w",
+        ])
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    wait_for_content(&output_path, "First");
+    // Watching starts once the first run is done
+    thread::sleep(Duration::from_millis(500));
+
+    // The input changes
+    fs::write(&schema_path, schema("Second")).unwrap();
+    wait_for_content(&output_path, "Second");
+
+    // A file it references changes
+    fs::write(&common_path, r#"{"description": "Shared"}"#).unwrap();
+    wait_for_content(&output_path, "Shared");
+
+    // A burst of writes converts once
+    for i in 1..=5 {
+        fs::write(&schema_path, schema(&format!("Burst {}", i))).unwrap();
+    }
+    wait_for_content(&output_path, "Burst 5");
+
+    // A schema that doesn't parse is reported and watching goes on
+    fs::write(&schema_path, "{ broken").unwrap();
+    thread::sleep(Duration::from_secs(1));
+    assert!(child.try_wait().unwrap().is_none());
+    fs::write(&schema_path, schema("Fixed")).unwrap();
+    wait_for_content(&output_path, "Fixed");
+
+    // The output is written just before the run is logged
+    thread::sleep(Duration::from_millis(500));
+    child.kill().unwrap();
+    child.wait().unwrap();
+    let mut stderr = String::new();
+    child
+        .stderr
+        .take()
+        .unwrap()
+        .read_to_string(&mut stderr)
+        .unwrap();
+    assert!(stderr.contains("Failed to parse JSON"), "{}", stderr);
+    // The first run, one per change and one for the burst
+    assert_eq!(
+        stderr.matches("Regenerated 'out.html'.").count(),
+        5,
+        "{}",
+        stderr
+    );
+}
+
+const OPENAPI_DOCUMENT: &str = r##"
+openapi: 3.0.3
+info:
+  title: Petstore
+  description: Pets for sale
+  version: 1.0.0
+paths: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [id, petType]
+      discriminator:
+        propertyName: petType
+      oneOf:
+        - $ref: '#/components/schemas/Cat'
+        - $ref: '#/components/schemas/Dog'
+      properties:
+        id:
+          type: integer
+          readOnly: true
+          example: 42
+        name:
+          type: string
+          nullable: true
+        weight:
+          type: number
+          minimum: 0
+          exclusiveMinimum: true
+        petType:
+          type: string
+    Cat:
+      type: object
+      properties:
+        indoor: {type: boolean}
+    Dog:
+      type: object
+      properties:
+        barks: {type: boolean}
+"##;
+
+#[test]
+fn test_openapi_components_as_definitions() {
+    cli()
+        .arg("convert")
+        .write_stdin(OPENAPI_DOCUMENT)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<h1 class="schema-title">Petstore</h1><p class="schema-description">Pets for sale</p>"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<div class="definitions-section"><h2>Definitions</h2><div class="definition" id="def-Cat">"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<span class="property-name">id</span><span class="type-badge">integer</span><span class="required-badge">required</span><span class="read-only-badge">read-only</span></div><div class="schema-details depth-1"><div class="examples"><span class="examples-label">Examples:</span><code>42</code>"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#"<span class="type-badge">string | null</span>"#,
+        ))
+        // OpenAPI 3.0 bounds are made exclusive with a boolean
+        .stdout(predicate::str::contains(
+            r#"<span class="constraint">min: &gt; 0</span>"#,
+        ))
+        .stdout(predicate::str::contains("paths").not());
+}
+
+#[test]
+fn test_openapi_discriminated_component() {
+    cli()
+        .arg("convert")
+        .write_stdin(OPENAPI_DOCUMENT)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r##"<div class="compound-schema"><h4>One Of</h4><div class="discriminator"><span class="discriminator-label">Discriminator:</span><code>petType</code></div><div class="compound-options"><div class="compound-option"><h5>Cat</h5><div class="schema-details depth-0"><div class="schema-ref"><span class="ref-label">See</span><a class="ref-link" href="#def-Cat">Cat</a></div>"##,
+        ))
+        .stdout(predicate::str::contains("<h5>Dog</h5>"));
+
+    cli()
+        .arg("convert")
+        .arg("--tabs")
+        .write_stdin(OPENAPI_DOCUMENT)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<input type="radio" class="tab-input" name="tab-1-oneOf" id="tab-1-oneOf-1" checked>"#,
+        ));
+}
+
+#[test]
+fn test_openapi_nested_discriminated_union() {
+    let document = r##"
+openapi: 3.1.0
+info: {title: Shop, version: 1.0.0}
+paths: {}
+components:
+  schemas:
+    Order:
+      type: object
+      properties:
+        pet:
+          oneOf:
+            - $ref: '#/components/schemas/Cat'
+            - $ref: '#/components/schemas/Dog'
+          discriminator:
+            propertyName: petType
+            mapping:
+              kitty: '#/components/schemas/Cat'
+    Cat:
+      type: object
+      properties:
+        indoor: {type: boolean}
+    Dog:
+      type: object
+      properties:
+        barks: {type: boolean}
+"##;
+
+    cli()
+        .arg("convert")
+        .write_stdin(document)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r##"<div class="property depth-0" data-property="pet"><div class="property-header"><span class="property-name">pet</span><span class="type-badge">oneOf</span></div><div class="schema-details depth-1"><div class="compound-schema"><h4>One Of</h4><div class="discriminator"><span class="discriminator-label">Discriminator:</span><code>petType</code></div><div class="compound-options"><div class="compound-option"><h5>kitty</h5>"##,
+        ))
+        .stdout(predicate::str::contains("<h5>Dog</h5>"));
+
+    cli()
+        .arg("convert")
+        .arg("--format")
+        .arg("markdown")
+        .write_stdin(document)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "| `pet` | `object` | no |  | One Of: kitty<br>Discriminator: `petType`<br>See [Cat](#def-Cat) |",
+        ))
+        .stdout(predicate::str::contains(
+            "| `pet` | `object` | no |  | One Of: Dog<br>Discriminator: `petType`<br>See [Dog](#def-Dog) |",
+        ));
+}
+
+#[test]
+fn test_openapi_markdown() {
+    cli()
+        .arg("convert")
+        .arg("--format")
+        .arg("markdown")
+        .write_stdin(OPENAPI_DOCUMENT)
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "# Petstore\n\nPets for sale\n\n## Definitions\n\n",
+        ))
+        .stdout(predicate::str::contains(
+            "#### One Of\n\nDiscriminator: `petType`\n\n##### Cat\n\nSee [Cat](#def-Cat)\n\n",
+        ))
+        .stdout(predicate::str::contains(
+            "| `name` | `string \\| null` | no |",
+        ));
+}
+
+#[test]
+fn test_openapi_strict() {
+    cli()
+        .arg("convert")
+        .arg("--strict")
+        .write_stdin(OPENAPI_DOCUMENT)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#"id="def-Pet""#));
+
+    cli()
+        .arg("validate")
+        .write_stdin(OPENAPI_DOCUMENT)
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_openapi_pointer() {
+    cli()
+        .arg("convert")
+        .arg("--pointer")
+        .arg("#/components/schemas/Pet")
+        .arg("--example")
+        .write_stdin(OPENAPI_DOCUMENT)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"<h1 class="schema-title">Pet</h1>"#,
+        ))
+        .stdout(predicate::str::contains(r#"data-property="petType""#))
+        // Referenced components are documented in place
+        .stdout(predicate::str::contains(
+            r##"<h4>Cat</h4><div class="schema-details depth-0"><div class="schema-ref"><span class="ref-label">See</span><code>#/components/schemas/Cat</code></div>"##,
+        ))
+        .stdout(predicate::str::contains("definitions-section").not())
+        .stdout(predicate::str::contains("&quot;id&quot;: 42"));
+}
+
+#[test]
+fn test_pointer_not_found() {
+    cli()
+        .arg("convert")
+        .arg("--pointer")
+        .arg("/components/schemas/Missing")
+        .write_stdin(OPENAPI_DOCUMENT)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "No schema at '/components/schemas/Missing' in the document.",
+        ));
+}
+
+const NETWORK_SCHEMA: &str = r##"{
+    "title": "Device",
+    "$defs": {
+        "NetworkConfig": {
+            "description": "Network settings",
+            "type": "object",
+            "properties": {
+                "address": {"$ref": "#/$defs/Address"},
+                "fallback": {"$ref": "#/$defs/NetworkConfig"}
+            }
+        },
+        "Address": {"type": "string", "format": "ipv4"},
+        "Network Name": {"title": "Name of the network", "type": "string"}
+    }
+}"##;
+
+#[test]
+fn test_pointer_to_definition() {
+    cli()
+        .arg("convert")
+        .arg("--pointer")
+        .arg("/$defs/NetworkConfig")
+        .write_stdin(NETWORK_SCHEMA)
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            r#"<div class="schema-container"><div class="schema-header"><h1 class="schema-title">NetworkConfig</h1><p class="schema-description">Network settings</p></div>"#,
+        ))
+        // References resolve against the whole document
+        .stdout(predicate::str::contains(
+            r##"<span class="ref-label">See</span><code>#/$defs/Address</code></div><div class="constraints"><span class="constraint">format: ipv4</span>"##,
+        ))
+        // and back to the selected schema are recursive
+        .stdout(predicate::str::contains(
+            r##"<div class="schema-ref recursive-ref"><span class="ref-label">recursive: see</span><code>#/$defs/NetworkConfig</code>"##,
+        ))
+        .stdout(predicate::str::contains("Device").not())
+        .stdout(predicate::str::contains("definitions-section").not());
+}
+
+#[test]
+fn test_pointer_as_uri_fragment() {
+    cli()
+        .arg("convert")
+        .arg("--format")
+        .arg("markdown")
+        .arg("--pointer")
+        .arg("#/$defs/Network%20Name")
+        .write_stdin(NETWORK_SCHEMA)
+        .assert()
+        .success()
+        .stdout("# Name of the network\n");
+}
+
+#[test]
+fn test_invalid_pointer() {
+    cli()
+        .arg("convert")
+        .arg("--pointer")
+        .arg("$defs/Address")
+        .write_stdin(NETWORK_SCHEMA)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid JSON Pointer '$defs/Address', pointers start with '/'",
+        ));
+}
@@ -0,0 +1,114 @@
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+use crate::support::cli;
+
+const SENSOR_SCHEMA: &str = r##"{
+    "type": "object",
+    "required": ["id", "kind"],
+    "properties": {
+        "id": {"type": "string", "format": "uuid"},
+        "kind": {"$ref": "#/$defs/Kind"},
+        "name": {"type": "string", "examples": ["Kitchen"]},
+        "interval": {"type": "integer", "minimum": 5, "default": 60}
+    },
+    "$defs": {"Kind": {"enum": ["temperature", "humidity"]}}
+}"##;
+
+#[test]
+fn test_example_help() {
+    cli()
+        .arg("example")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Generate an example document matching a JSON Schema",
+        ))
+        .stdout(predicate::str::contains("--required-only"));
+}
+
+#[test]
+fn test_example_json() {
+    cli()
+        .arg("example")
+        .write_stdin(SENSOR_SCHEMA)
+        .assert()
+        .success()
+        .stdout(
+            "{\n  \"id\": \"123e4567-e89b-12d3-a456-426614174000\",\n  \"interval\": 60,\n  \"kind\": \"temperature\",\n  \"name\": \"Kitchen\"\n}\n",
+        );
+}
+
+#[test]
+fn test_example_yaml_required_only() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("schema.json");
+    let output_path = temp_dir.path().join("example.yaml");
+    fs::write(&input_path, SENSOR_SCHEMA).unwrap();
+
+    cli()
+        .arg("example")
+        .arg("-i")
+        .arg(&input_path)
+        .arg("-o")
+        .arg(&output_path)
+        .arg("--format")
+        .arg("yaml")
+        .arg("--required-only")
+        .assert()
+        .success();
+
+    assert_eq!(
+        fs::read_to_string(&output_path).unwrap(),
+        "id: 123e4567-e89b-12d3-a456-426614174000\nkind: temperature\n"
+    );
+}
+
+#[test]
+fn test_example_pointer() {
+    cli()
+        .arg("example")
+        .arg("--pointer")
+        .arg("/$defs/Kind")
+        .write_stdin(SENSOR_SCHEMA)
+        .assert()
+        .success()
+        .stdout("\"temperature\"\n");
+}
+
+#[test]
+fn test_convert_with_example() {
+    cli()
+        .arg("convert")
+        .arg("--example")
+        .write_stdin(SENSOR_SCHEMA)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "<div class=\"example-section\"><h2>Example</h2><pre class=\"example\"><code>{\n  &quot;id&quot;: &quot;123e4567-e89b-12d3-a456-426614174000&quot;,\n",
+        ));
+
+    cli()
+        .arg("convert")
+        .arg("--format")
+        .arg("markdown")
+        .arg("--example")
+        .write_stdin(SENSOR_SCHEMA)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "## Example\n\n```json\n{\n  \"id\": \"123e4567-e89b-12d3-a456-426614174000\",\n",
+        ));
+}
+
+#[test]
+fn test_convert_without_example() {
+    cli()
+        .arg("convert")
+        .write_stdin(SENSOR_SCHEMA)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("example-section").not());
+}
@@ -1,9 +1,16 @@
 use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
 
 use crate::support::cli;
 
+pub mod check;
 pub mod convert;
+pub mod example;
+pub mod serve;
+pub mod site;
 pub mod upgrade;
+pub mod validate;
 
 #[test]
 fn test_cli_version() {
@@ -22,8 +29,13 @@ fn test_cli_help() {
         .success()
         .stdout(predicate::str::contains("A Rust CLI application template"))
         .stdout(predicate::str::contains("Commands:"))
+        .stdout(predicate::str::contains("check"))
         .stdout(predicate::str::contains("convert"))
-        .stdout(predicate::str::contains("upgrade"));
+        .stdout(predicate::str::contains("serve"))
+        .stdout(predicate::str::contains("site"))
+        .stdout(predicate::str::contains("example"))
+        .stdout(predicate::str::contains("upgrade"))
+        .stdout(predicate::str::contains("validate"));
 }
 
 #[test]
@@ -140,3 +152,209 @@ fn test_short_command_aliases() {
         .success()
         .stdout(predicate::str::contains("Upgrade"));
 }
+
+#[test]
+fn test_config_supplies_convert_defaults() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("deckard.yaml");
+
+    fs::write(
+        temp_dir.path().join("schema.json"),
+        r#"{"type":"object","properties":{"name":{"type":"string"}}}"#,
+    )
+    .unwrap();
+    fs::write(
+        &config_path,
+        "convert:\n  input: schema.json\n  output: doc.html\n  minify: false\n",
+    )
+    .unwrap();
+
+    cli()
+        .arg("-C")
+        .arg(&config_path)
+        .arg("convert")
+        .assert()
+        .success();
+
+    let output = fs::read_to_string(temp_dir.path().join("doc.html")).unwrap();
+    assert!(output.contains("data-property=\"name\""));
+}
+
+#[test]
+fn test_config_default_path_in_working_directory() {
+    let temp_dir = TempDir::new().unwrap();
+
+    fs::write(
+        temp_dir.path().join("deckard.yaml"),
+        "convert:\n  input: schema.json\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("schema.json"),
+        r#"{"description":"From config"}"#,
+    )
+    .unwrap();
+
+    cli()
+        .current_dir(temp_dir.path())
+        .arg("convert")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("From config"));
+}
+
+#[test]
+fn test_config_overridden_by_flags() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("deckard.json");
+
+    fs::write(
+        temp_dir.path().join("config-schema.json"),
+        r#"{"description":"From config"}"#,
+    )
+    .unwrap();
+    fs::write(
+        &config_path,
+        r#"{"convert":{"input":"config-schema.json","minify":false}}"#,
+    )
+    .unwrap();
+
+    let schema_path = temp_dir.path().join("flag-schema.json");
+    fs::write(&schema_path, r#"{"description":"From flag"}"#).unwrap();
+
+    cli()
+        .arg("convert")
+        .arg("--config")
+        .arg(&config_path)
+        .arg("--minify")
+        .arg("-i")
+        .arg(&schema_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("From flag"))
+        .stdout(predicate::str::contains("From config").not())
+        .stdout(predicate::str::contains(
+            r#"<div class="schema-container"><div class="schema-header">"#,
+        ));
+}
+
+#[test]
+fn test_config_jobs() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("deckard.yaml");
+    fs::write(&config_path, "convert:\n  jobs: 13\n").unwrap();
+
+    cli()
+        .arg("-C")
+        .arg(&config_path)
+        .arg("-L")
+        .arg("debug")
+        .arg("convert")
+        .write_stdin(r#"{"type":"object"}"#)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("jobs: 13"));
+
+    cli()
+        .arg("-C")
+        .arg(&config_path)
+        .arg("-L")
+        .arg("debug")
+        .arg("convert")
+        .arg("--jobs")
+        .arg("17")
+        .write_stdin(r#"{"type":"object"}"#)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("jobs: 17"));
+}
+
+#[test]
+fn test_config_pointer() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("deckard.yaml");
+    fs::write(
+        temp_dir.path().join("schema.json"),
+        r#"{"$defs":{"Light":{"description":"A light"},"Fan":{"description":"A fan"}}}"#,
+    )
+    .unwrap();
+    fs::write(
+        &config_path,
+        "convert:\n  input: schema.json\n  pointer: /$defs/Light\n",
+    )
+    .unwrap();
+
+    cli()
+        .arg("-C")
+        .arg(&config_path)
+        .arg("convert")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("A light"))
+        .stdout(predicate::str::contains("A fan").not());
+
+    cli()
+        .arg("-C")
+        .arg(&config_path)
+        .arg("convert")
+        .arg("--pointer")
+        .arg("/$defs/Fan")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("A fan"))
+        .stdout(predicate::str::contains("A light").not());
+}
+
+#[test]
+fn test_config_explicit_missing() {
+    cli()
+        .arg("-C")
+        .arg("/nonexistent/deckard.yaml")
+        .arg("convert")
+        .write_stdin(r#"{"type":"object"}"#)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Config file '/nonexistent/deckard.yaml' not found.",
+        ));
+}
+
+#[test]
+fn test_config_malformed() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("deckard.yaml");
+    fs::write(&config_path, "convert:\n  inptu: schema.json\n").unwrap();
+
+    cli()
+        .arg("-C")
+        .arg(&config_path)
+        .arg("convert")
+        .write_stdin(r#"{"type":"object"}"#)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to parse config file"))
+        .stderr(predicate::str::contains("inptu"));
+}
+
+#[test]
+fn test_config_only_read_by_commands_using_it() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("deckard.yaml"), "convert: [broken\n").unwrap();
+    fs::write(temp_dir.path().join("schema.json"), r#"{"type":"object"}"#).unwrap();
+
+    cli()
+        .current_dir(temp_dir.path())
+        .arg("validate")
+        .write_stdin(r#"{"type":"object"}"#)
+        .assert()
+        .success();
+
+    cli()
+        .current_dir(temp_dir.path())
+        .arg("convert")
+        .arg("-i")
+        .arg("schema.json")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to parse config file"));
+}
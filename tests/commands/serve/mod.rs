@@ -0,0 +1,29 @@
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+use crate::support::cli;
+
+#[test]
+fn test_serve_help() {
+    cli()
+        .arg("serve")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Preview rendered schemas in the browser",
+        ))
+        .stdout(predicate::str::contains("--port"));
+}
+
+#[test]
+fn test_serve_missing_directory() {
+    let temp_dir = TempDir::new().unwrap();
+
+    cli()
+        .arg("serve")
+        .arg(temp_dir.path().join("missing"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unable to serve"));
+}
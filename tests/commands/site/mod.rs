@@ -0,0 +1,99 @@
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+use crate::support::cli;
+
+#[test]
+fn test_site_help() {
+    cli()
+        .arg("site")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Generate a documentation site from a directory of schemas",
+        ))
+        .stdout(predicate::str::contains("--output"))
+        .stdout(predicate::str::contains("--title"));
+}
+
+#[test]
+fn test_site_generates_linked_pages() {
+    let temp_dir = TempDir::new().unwrap();
+    let schemas = temp_dir.path().join("schemas");
+    fs::create_dir_all(schemas.join("devices")).unwrap();
+    fs::write(
+        schemas.join("common.json"),
+        r#"{"title": "Common", "$defs": {"Id": {"type": "string"}}}"#,
+    )
+    .unwrap();
+    fs::write(
+        schemas.join("devices/light.json"),
+        r#"{"title": "Light", "properties": {"id": {"$ref": "../common.json#/$defs/Id"}}}"#,
+    )
+    .unwrap();
+    let output = temp_dir.path().join("site");
+
+    cli()
+        .arg("-vv")
+        .arg("site")
+        .arg(&schemas)
+        .arg("--output")
+        .arg(&output)
+        .arg("--title")
+        .arg("Device Schemas")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("Generated 2 of 2 page(s)"));
+
+    let light = fs::read_to_string(output.join("devices/light.html")).unwrap();
+    assert!(light.contains(r#"href="../common.html#def-Id""#));
+    assert!(light.contains(r#"<a href="../index.html">Device Schemas</a>"#));
+
+    let index = fs::read_to_string(output.join("index.html")).unwrap();
+    assert!(index.contains("<title>Device Schemas</title>"));
+    assert!(index.contains(r#"href="devices/light.html""#));
+    assert!(output.join("search-index.json").exists());
+}
+
+#[test]
+fn test_site_reports_failures() {
+    let temp_dir = TempDir::new().unwrap();
+    let schemas = temp_dir.path().join("schemas");
+    fs::create_dir_all(&schemas).unwrap();
+    fs::write(schemas.join("good.json"), r#"{"type": "object"}"#).unwrap();
+    fs::write(schemas.join("bad.json"), "{ broken").unwrap();
+    let output = temp_dir.path().join("site");
+
+    cli()
+        .arg("-vv")
+        .arg("site")
+        .arg(&schemas)
+        .arg("-o")
+        .arg(&output)
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("Generated 1 of 2 page(s)"))
+        .stderr(predicate::str::contains("1 schema(s) failed to render"))
+        .stderr(predicate::str::contains("bad.json"));
+
+    assert!(output.join("good.html").exists());
+    assert!(output.join("index.html").exists());
+}
+
+#[test]
+fn test_site_missing_directory() {
+    let temp_dir = TempDir::new().unwrap();
+
+    cli()
+        .arg("site")
+        .arg(temp_dir.path().join("missing"))
+        .arg("-o")
+        .arg(temp_dir.path().join("site"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not a directory"));
+}
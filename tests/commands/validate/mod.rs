@@ -0,0 +1,142 @@
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+use crate::support::cli;
+
+#[test]
+fn test_validate_help() {
+    cli()
+        .arg("validate")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Check that the input is a well-formed JSON Schema",
+        ))
+        .stdout(predicate::str::contains("--input"));
+}
+
+#[test]
+fn test_validate_valid_schema() {
+    cli()
+        .arg("validate")
+        .write_stdin(
+            r#"{"type":"object","required":["name"],"properties":{"name":{"type":"string"}}}"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    cli()
+        .arg("-vv")
+        .arg("validate")
+        .write_stdin(r#"{"type":"string"}"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("'stdin' is a valid JSON Schema."));
+}
+
+#[test]
+fn test_validate_reports_problems() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("schema.json");
+    fs::write(
+        &input_path,
+        r##"{
+            "type": "object",
+            "requird": ["name"],
+            "properties": {
+                "name": {"type": "strng"},
+                "owner": {"$ref": "#/$defs/User"},
+                "size": {"minimum": "large"}
+            }
+        }"##,
+    )
+    .unwrap();
+
+    cli()
+        .arg("validate")
+        .arg("-i")
+        .arg(&input_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not a valid JSON Schema"))
+        .stderr(predicate::str::contains(
+            "#/requird: unknown keyword 'requird', did you mean 'required'?",
+        ))
+        .stderr(predicate::str::contains(
+            "#/properties/name/type: invalid type 'strng', did you mean 'string'?",
+        ))
+        .stderr(predicate::str::contains(
+            "#/properties/owner/$ref: unresolved reference '#/$defs/User'",
+        ))
+        .stderr(predicate::str::contains("#/properties/size/minimum:"));
+}
+
+#[test]
+fn test_validate_external_refs() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("common.yaml"),
+        "$defs:\n  Id:\n    type: string\n",
+    )
+    .unwrap();
+    let input_path = temp_dir.path().join("schema.yaml");
+    fs::write(
+        &input_path,
+        "properties:\n  id:\n    $ref: common.yaml#/$defs/Id\n  other:\n    $ref: missing.yaml\n",
+    )
+    .unwrap();
+
+    cli()
+        .arg("validate")
+        .arg("-i")
+        .arg(&input_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("common.yaml").not())
+        .stderr(predicate::str::contains(
+            "#/properties/other/$ref: unresolved reference 'missing.yaml'",
+        ));
+}
+
+#[test]
+fn test_convert_strict() {
+    let schema = r#"{"type":"object","properties":{"name":{"type":"string","minLenght":1}}}"#;
+
+    cli()
+        .arg("convert")
+        .write_stdin(schema)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("schema-container"));
+
+    cli()
+        .arg("convert")
+        .arg("--strict")
+        .write_stdin(schema)
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains(
+            "#/properties/name/minLenght: unknown keyword 'minLenght', did you mean 'minLength'?",
+        ));
+}
+
+#[test]
+fn test_convert_strict_from_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("deckard.yaml");
+    fs::write(&config_path, "convert:\n  strict: true\n").unwrap();
+
+    cli()
+        .arg("-C")
+        .arg(&config_path)
+        .arg("convert")
+        .write_stdin(r#"{"type":"strng"}"#)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid type 'strng'"));
+}